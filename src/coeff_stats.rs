@@ -0,0 +1,107 @@
+use crate::convolution_polynomial::{center_lift, ConvPoly};
+use crate::error::NtruError;
+use std::collections::BTreeMap;
+
+// Statistics about the coefficients of a polynomial in (Z/mZ)[x]/(x^N - 1). Every statistic is computed on the
+// center-lifted representative, whose coefficients lie in (-m/2, m/2], so that they describe the "size" of the
+// polynomial the way decryption sees it: a ternary key stored modulo q still has only +1s, -1s and 0s, and the noise
+// in a(x) ≡ e(x)*f(x) (mod q) can be measured before it's center-lifted.
+
+/// The coefficients of a polynomial in (Z/mZ)\[x\]/(x^N - 1), center-lifted into (-m/2, m/2\] and counted
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoeffStats {
+    /// The number of coefficients N, including any zeros past the degree of the polynomial
+    pub n: usize,
+    /// The modulus the coefficients were center-lifted by
+    pub modulus: i32,
+    /// The center-lifted polynomial the statistics describe
+    pub centered: ConvPoly,
+    /// The number of coefficients taking each value, omitting values that don't occur
+    pub histogram: BTreeMap<i32, usize>,
+}
+
+impl CoeffStats {
+    /// Computes the statistics of a polynomial in (Z/mZ)\[x\]/(x^n - 1). Coefficients of degree n and above are
+    /// folded into place before center-lifting. Returns an error if n is 0 or the modulus `m` is not a positive
+    /// integer.
+    pub fn new(poly: &ConvPoly, n: usize, m: i32) -> Result<CoeffStats, NtruError> {
+        if n == 0 {
+            return Err(NtruError::InvalidParameters("N must be positive"));
+        }
+        if m <= 0 {
+            return Err(NtruError::InvalidModulus(m));
+        }
+        let mut folded = vec![0i64; n];
+        for (i, &c) in poly.coeffs.iter().enumerate() {
+            folded[i % n] += c as i64;
+        }
+        let coeffs = folded
+            .into_iter()
+            .map(|c| center_lift(c.rem_euclid(m as i64) as i32, m))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut histogram = BTreeMap::new();
+        for &c in &coeffs {
+            *histogram.entry(c).or_insert(0) += 1;
+        }
+
+        Ok(CoeffStats {
+            n,
+            modulus: m,
+            centered: ConvPoly { coeffs }.trim(),
+            histogram,
+        })
+    }
+
+    /// Returns the number of coefficients equal to `value`
+    pub fn count(&self, value: i32) -> usize {
+        self.histogram.get(&value).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of coefficients equal to 1
+    pub fn ones(&self) -> usize {
+        self.count(1)
+    }
+
+    /// Returns the number of coefficients equal to -1
+    pub fn neg_ones(&self) -> usize {
+        self.count(-1)
+    }
+
+    /// Returns the number of coefficients equal to 0
+    pub fn zeros(&self) -> usize {
+        self.count(0)
+    }
+
+    /// Returns the number of non-zero coefficients (the Hamming weight)
+    pub fn weight(&self) -> usize {
+        self.n - self.zeros()
+    }
+
+    /// Returns whether the polynomial lies in T(d1, d2), i.e. has exactly `d1` coefficients equal to 1, `d2` equal to
+    /// -1 and all others 0
+    pub fn is_ternary(&self, d1: usize, d2: usize) -> bool {
+        self.ones() == d1 && self.neg_ones() == d2 && self.zeros() + d1 + d2 == self.n
+    }
+
+    /// Returns the L1 norm of the center-lifted polynomial
+    pub fn l1_norm(&self) -> u64 {
+        self.centered.l1_norm()
+    }
+
+    /// Returns the L2 norm of the center-lifted polynomial
+    pub fn l2_norm(&self) -> f64 {
+        self.centered.l2_norm()
+    }
+
+    /// Returns the infinity norm of the center-lifted polynomial
+    pub fn linf_norm(&self) -> u32 {
+        self.centered.linf_norm()
+    }
+
+    /// Returns the centered norm of the center-lifted polynomial. See [`ConvPoly::centered_norm`].
+    pub fn centered_norm(&self) -> f64 {
+        // n is positive, so this can't fail
+        self.centered.centered_norm(self.n).unwrap_or(0.0)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::convolution_polynomial::ConvPoly;
+use crate::error::NtruError;
+use crate::poly_ring::{PolyRing, RingElement};
+
+// Arithmetic is done on RingElement, whose i32 coefficients leave room for any modulus, but keys and ciphertexts only
+// ever hold coefficients modulo q (at most 2^16 for every standardized parameter set) or modulo p (3). Storing those
+// as u16 and i8 halves and quarters the memory they take. Neither type records its modulus or N: like the parameter
+// set a key was generated with, they're supplied by the ring the polynomial is converted back into.
+
+/// The largest modulus whose reduced coefficients fit in a u16
+pub(crate) const MAX_MODULUS_Q: i32 = 1 << 16;
+/// The largest modulus whose center-lifted coefficients fit in an i8
+pub(crate) const MAX_MODULUS_P: i32 = 255;
+
+/// An element of (Z/qZ)\[x\]/(x^N - 1) stored with 16-bit coefficients in \[0, q), for q of at most 2^16
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolyModQ {
+    /// The coefficients, from the constant term up
+    coeffs: Vec<u16>,
+}
+
+impl PolyModQ {
+    /// Constructs a polynomial from its coefficients, which aren't checked against any modulus until it's converted
+    /// into a ring element
+    pub fn new(coeffs: Vec<u16>) -> PolyModQ {
+        PolyModQ { coeffs }
+    }
+
+    /// Compacts a ring element. Returns an error if the ring's modulus is larger than 2^16.
+    pub fn from_element(element: &RingElement) -> Result<PolyModQ, NtruError> {
+        let modulus = element.ring().modulus();
+        if modulus > MAX_MODULUS_Q {
+            return Err(NtruError::InvalidModulus(modulus));
+        }
+        Ok(PolyModQ {
+            coeffs: element.coeffs().iter().map(|&c| c as u16).collect(),
+        })
+    }
+
+    /// Returns the number of coefficients
+    pub fn n(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Returns the coefficients, from the constant term up
+    pub fn coeffs(&self) -> &[u16] {
+        &self.coeffs
+    }
+
+    /// Expands the polynomial into an element of the given ring. Returns an error if it doesn't have exactly N
+    /// coefficients or any coefficient isn't reduced modulo the ring's modulus.
+    pub fn to_element(&self, ring: &PolyRing) -> Result<RingElement, NtruError> {
+        if self.coeffs.len() != ring.n() {
+            return Err(NtruError::ParameterMismatch(
+                "polynomial doesn't have N coefficients",
+            ));
+        }
+        if self.coeffs.iter().any(|&c| c as i32 >= ring.modulus()) {
+            return Err(NtruError::InvalidEncoding(
+                "polynomial coefficients must be reduced modulo q",
+            ));
+        }
+        Ok(ring.element(&self.to_poly()))
+    }
+
+    /// Returns the polynomial with i32 coefficients, with trailing zeros removed
+    pub fn to_poly(&self) -> ConvPoly {
+        ConvPoly {
+            coeffs: self.coeffs.iter().map(|&c| c as i32).collect(),
+        }
+        .trim()
+    }
+
+    /// Serializes the polynomial into a big-endian byte vector. Each coefficient is represented by 2 bytes.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        self.coeffs.iter().flat_map(|c| c.to_be_bytes()).collect()
+    }
+
+    /// Deserializes a big-endian byte vector into a polynomial. Returns an error if the buffer's length isn't a
+    /// multiple of 2.
+    pub fn from_be_bytes(buf: &[u8]) -> Result<PolyModQ, NtruError> {
+        if !buf.len().is_multiple_of(size_of::<u16>()) {
+            return Err(NtruError::InvalidEncoding(
+                "buffer length must be a multiple of 2",
+            ));
+        }
+        Ok(PolyModQ {
+            coeffs: buf
+                .chunks_exact(size_of::<u16>())
+                .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+                .collect(),
+        })
+    }
+}
+
+/// An element of (Z/pZ)\[x\]/(x^N - 1) stored with 8-bit coefficients center-lifted into (-p/2, p/2\], for p of at
+/// most 255. For p = 3 these are exactly the ternary coefficients -1, 0 and 1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolyModP {
+    /// The center-lifted coefficients, from the constant term up
+    coeffs: Vec<i8>,
+}
+
+impl PolyModP {
+    /// Compacts a ring element. Returns an error if the ring's modulus is larger than 255.
+    pub fn from_element(element: &RingElement) -> Result<PolyModP, NtruError> {
+        let modulus = element.ring().modulus();
+        if modulus > MAX_MODULUS_P {
+            return Err(NtruError::InvalidModulus(modulus));
+        }
+        Ok(PolyModP {
+            coeffs: element
+                .center_lift_constant_time()
+                .into_iter()
+                .map(|c| c as i8)
+                .collect(),
+        })
+    }
+
+    /// Returns the number of coefficients
+    pub fn n(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Returns the center-lifted coefficients, from the constant term up
+    pub fn coeffs(&self) -> &[i8] {
+        &self.coeffs
+    }
+
+    /// Expands the polynomial into an element of the given ring. Returns an error if it doesn't have exactly N
+    /// coefficients.
+    pub fn to_element(&self, ring: &PolyRing) -> Result<RingElement, NtruError> {
+        if self.coeffs.len() != ring.n() {
+            return Err(NtruError::ParameterMismatch(
+                "polynomial doesn't have N coefficients",
+            ));
+        }
+        Ok(ring.element(&self.to_poly()))
+    }
+
+    /// Returns the center-lifted polynomial with i32 coefficients, with trailing zeros removed
+    pub fn to_poly(&self) -> ConvPoly {
+        ConvPoly {
+            coeffs: self.coeffs.iter().map(|&c| c as i32).collect(),
+        }
+        .trim()
+    }
+}
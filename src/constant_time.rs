@@ -0,0 +1,325 @@
+use crate::error::NtruError;
+use crate::poly_ring::ReductionPoly;
+use rand::Rng;
+
+// Branches and memory accesses that depend on secret data leak it through timing. The helpers below select between
+// values with bit masks rather than branches, and the inversion runs a fixed number of fixed-length iterations for a
+// given N and modulus, whatever the polynomial being inverted.
+//
+// The same goes for the rest of decryption and key generation. Division takes a time that depends on its operands on
+// many processors, so coefficients are reduced with a precomputed Barrett factor instead of `%` or `rem_euclid`.
+// Every operation takes and returns exactly N coefficients, where trimming trailing zeros would reveal the degree,
+// and products work through every pair of coefficients rather than skipping zeros. Ternary polynomials are sampled by
+// sorting randomly tagged positions with a fixed network of compare-exchanges, and expanded by comparing every
+// position with every index, so that no memory access is made at a secret position.
+
+/// The number of low bits of a sorting key that hold the position it tags
+const POSITION_BITS: u32 = 20;
+
+/// Returns -1 (every bit set) if x is negative and 0 otherwise
+pub(crate) fn negative_mask(x: i64) -> i64 {
+    x >> 63
+}
+
+/// Returns -1 (every bit set) if x is non-zero and 0 otherwise
+pub(crate) fn nonzero_mask(x: i64) -> i64 {
+    negative_mask(x | x.wrapping_neg())
+}
+
+/// A positive modulus m with the Barrett factor floor(2^64 / m), which reduces any i64 with two multiplications and
+/// no division
+struct Modulus {
+    m: i64,
+    factor: i128,
+}
+
+impl Modulus {
+    /// Precomputes the Barrett factor of a modulus, which must be positive
+    fn new(m: i64) -> Modulus {
+        Modulus {
+            m,
+            factor: (1i128 << 64) / m as i128,
+        }
+    }
+
+    /// Checks that the modulus is a positive integer before precomputing its Barrett factor
+    fn checked(m: i32) -> Result<Modulus, NtruError> {
+        if m <= 0 {
+            return Err(NtruError::InvalidModulus(m));
+        }
+        Ok(Modulus::new(m as i64))
+    }
+
+    /// Returns x mod m within \[0, m). The estimated quotient is off by at most 1, leaving x - quotient*m within
+    /// \[-m, 2m), which a masked addition and subtraction of m correct. quotient*m itself can fall just outside the
+    /// range of an i64, but wrapping arithmetic still gets the small difference right.
+    fn reduce(&self, x: i64) -> i64 {
+        let quotient = ((x as i128 * self.factor) >> 64) as i64;
+        let r = x.wrapping_sub(quotient.wrapping_mul(self.m));
+        let r = r + (self.m & negative_mask(r));
+        r - (self.m & !negative_mask(r - self.m))
+    }
+
+    /// Returns base^exp mod m. Only the exponent, which must not be secret, decides which steps are taken.
+    fn pow(&self, base: i64, mut exp: u64) -> i64 {
+        let (mut base, mut result) = (self.reduce(base), self.reduce(1));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.reduce(result * base);
+            }
+            base = self.reduce(base * base);
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+/// Reduces every coefficient modulo m into \[0, m), keeping all of them. Unlike [`crate::ConvPoly::modulo`], the time
+/// this takes depends only on the number of coefficients. Returns an error if the modulus `m` is not a positive
+/// integer.
+pub fn modulo<T: Copy + Into<i64>>(coeffs: &[T], m: i32) -> Result<Vec<i32>, NtruError> {
+    let modulus = Modulus::checked(m)?;
+    Ok(coeffs
+        .iter()
+        .map(|&c| modulus.reduce(c.into()) as i32)
+        .collect())
+}
+
+/// Center-lifts every coefficient modulo m into (-m/2, m/2\], keeping all of them. Unlike
+/// [`crate::ConvPoly::center_lift`], the time this takes depends only on the number of coefficients. Returns an error
+/// if the modulus `m` is not a positive integer.
+pub fn center_lift<T: Copy + Into<i64>>(coeffs: &[T], m: i32) -> Result<Vec<i32>, NtruError> {
+    let modulus = Modulus::checked(m)?;
+    let (m, half) = (m as i64, m as i64 / 2);
+    Ok(coeffs
+        .iter()
+        .map(|&c| {
+            let c = modulus.reduce(c.into());
+            (c - (m & negative_mask(half - c))) as i32
+        })
+        .collect())
+}
+
+/// Returns the product of a(x) and b(x) within the ring (Z/mZ)\[x\]/(f(x)), given exactly N coefficients of each for
+/// the reduction polynomial f(x) of degree N, as N coefficients within \[0, m). Every pair of coefficients is
+/// multiplied, whatever their values, and only the reduction polynomial, which isn't secret, decides which steps are
+/// taken. Returns an error if the modulus `m` is not a positive integer, the reduction polynomial isn't monic with
+/// positive degree, or either operand doesn't have N coefficients.
+pub fn product<A, B>(
+    a: &[A],
+    b: &[B],
+    reduction: &ReductionPoly,
+    m: i32,
+) -> Result<Vec<i32>, NtruError>
+where
+    A: Copy + Into<i64>,
+    B: Copy + Into<i64>,
+{
+    let modulus = Modulus::checked(m)?;
+    reduction.check()?;
+    let n = reduction.degree();
+    if a.len() != n || b.len() != n {
+        return Err(NtruError::ParameterMismatch(
+            "operands don't have N coefficients",
+        ));
+    }
+    let reduce_all = |coeffs: &mut dyn Iterator<Item = i64>| -> Vec<i64> {
+        coeffs.map(|c| modulus.reduce(c)).collect()
+    };
+    let a = reduce_all(&mut a.iter().map(|&c| c.into()));
+    let b = reduce_all(&mut b.iter().map(|&c| c.into()));
+
+    // Every product of reduced coefficients is below m^2, so at least one and up to this many of them can be added to
+    // a reduced sum before it might overflow
+    let batch = (i64::MAX - m as i64) / (m as i64 * m as i64);
+    let mut linear = vec![0; 2 * n - 1];
+    for (k, coeff) in linear.iter_mut().enumerate() {
+        let (mut sum, mut count) = (0, 0);
+        for i in k.saturating_sub(n - 1)..=k.min(n - 1) {
+            sum += a[i] * b[k - i];
+            count += 1;
+            if count == batch {
+                sum = modulus.reduce(sum);
+                count = 0;
+            }
+        }
+        *coeff = modulus.reduce(sum);
+    }
+
+    // Cancel the coefficients of x^(2N - 2) down to x^N against f(x), skipping its zero coefficients, whose positions
+    // are public
+    let low = reduce_all(&mut reduction.coeffs()[..n].iter().map(|&c| c as i64));
+    for i in (n..2 * n - 1).rev() {
+        let lead = linear[i];
+        for (j, &c) in low.iter().enumerate().filter(|(_, &c)| c != 0) {
+            linear[i - n + j] = modulus.reduce(linear[i - n + j] - lead * c);
+        }
+    }
+    linear.truncate(n);
+    Ok(linear.into_iter().map(|c| c as i32).collect())
+}
+
+/// Expands a ternary polynomial given by the positions of its 1 and -1 coefficients into its N coefficients.
+/// Positions are compared against every index rather than written to, so the memory accessed doesn't depend on them.
+/// Positions of N and above are ignored.
+pub fn expand_ternary(n: usize, ones: &[usize], neg_ones: &[usize]) -> Vec<i32> {
+    let matches = |positions: &[usize], j: usize| -> i64 {
+        positions
+            .iter()
+            .map(|&i| 1 & !nonzero_mask((i ^ j) as i64))
+            .sum()
+    };
+    (0..n)
+        .map(|j| (matches(ones, j) - matches(neg_ones, j)) as i32)
+        .collect()
+}
+
+/// Picks `num_ones` positions for 1s and `num_neg_ones` for -1s among N at random, each in increasing order. Every
+/// position is tagged with random high bits and the tags are sorted, so the first positions are a uniformly random
+/// choice; only N and the counts decide which steps are taken. Returns an error if there are more 1s and -1s than
+/// positions, or N doesn't fit in the bits reserved for it.
+pub(crate) fn random_positions(
+    n: usize,
+    num_ones: usize,
+    num_neg_ones: usize,
+) -> Result<(Vec<usize>, Vec<usize>), NtruError> {
+    if num_ones.saturating_add(num_neg_ones) > n {
+        return Err(NtruError::InvalidParameters(
+            "number of 1s and -1s should be <= n (the number of terms in the polynomial)",
+        ));
+    }
+    if n > 1 << POSITION_BITS {
+        return Err(NtruError::InvalidParameters(
+            "N is too large to sample in constant time",
+        ));
+    }
+
+    let mut rng = rand::thread_rng();
+    // 40 random bits above the position, which keeps every key positive
+    let mut keys: Vec<i64> = (0..n)
+        .map(|i| ((rng.gen::<u64>() >> 24 << POSITION_BITS) | i as u64) as i64)
+        .collect();
+    sort(&mut keys);
+
+    let position_mask = (1 << POSITION_BITS) - 1;
+    let mut chosen: Vec<i64> = keys[..num_ones + num_neg_ones]
+        .iter()
+        .map(|&key| key & position_mask)
+        .collect();
+    let (ones, neg_ones) = chosen.split_at_mut(num_ones);
+    sort(ones);
+    sort(neg_ones);
+    let to_positions = |keys: &[i64]| keys.iter().map(|&key| key as usize).collect();
+    Ok((to_positions(ones), to_positions(neg_ones)))
+}
+
+/// Sorts the values into increasing order with djbsort's network of compare-exchanges (as in NTRU Prime's reference
+/// code), whose sequence depends only on how many values there are
+fn sort(x: &mut [i64]) {
+    let n = x.len();
+    if n < 2 {
+        return;
+    }
+    let mut top = 1;
+    while top < n - top {
+        top += top;
+    }
+
+    let mut p = top;
+    while p > 0 {
+        for i in (0..n - p).filter(|i| i & p == 0) {
+            let (low, high) = x.split_at_mut(i + p);
+            compare_exchange(&mut low[i], &mut high[0]);
+        }
+        let mut i = 0;
+        let mut q = top;
+        while q > p {
+            while i < n - q {
+                if i & p == 0 {
+                    let mut a = x[i + p];
+                    let mut r = q;
+                    while r > p {
+                        compare_exchange(&mut a, &mut x[i + r]);
+                        r >>= 1;
+                    }
+                    x[i + p] = a;
+                }
+                i += 1;
+            }
+            q >>= 1;
+        }
+        p >>= 1;
+    }
+}
+
+/// Puts the smaller of two non-negative values in `a` and the larger in `b`
+fn compare_exchange(a: &mut i64, b: &mut i64) {
+    let swap = negative_mask(*b - *a) & (*a ^ *b);
+    *a ^= swap;
+    *b ^= swap;
+}
+
+/// Returns the inverse of a(x) within the ring (Z/mZ)\[x\]/(f(x)) for a prime m below 2^31, given the n coefficients
+/// of a(x) and the n + 1 coefficients of the monic f(x) of degree n, all reduced modulo m, or None if it isn't
+/// invertible.
+///
+/// This is the Bernstein-Yang "divstep" inversion (as in NTRU Prime's reference code), with f(x) and g(x) = a(x) both
+/// stored reversed. Every iteration conditionally swaps f and g (if the difference delta of their degrees calls for it
+/// and g has a non-zero constant term), then cancels the constant term of g against f and divides g by x, while v and
+/// r track the Bezout coefficients of g. After 2n - 1 iterations f holds the gcd, which is a constant exactly when
+/// delta is 0, and v its cofactor.
+pub(crate) fn inverse_mod_prime(a: &[u64], modulus_poly: &[u64], m: u64) -> Option<Vec<u64>> {
+    let n = modulus_poly.len() - 1;
+    let modulus = Modulus::new(m as i64);
+    let mut f: Vec<u64> = modulus_poly.iter().rev().copied().collect();
+    let mut g = vec![0; n + 1];
+    let mut v = vec![0; n + 1];
+    let mut r = vec![0; n + 1];
+    for (i, &coeff) in a.iter().enumerate() {
+        g[n - 1 - i] = coeff;
+    }
+    r[0] = 1;
+    let mut delta: i64 = 1;
+
+    for _ in 0..2 * n - 1 {
+        // v(x) <-- x*v(x)
+        v.copy_within(0..n, 1);
+        v[0] = 0;
+
+        // Swap (f, v) with (g, r) if delta > 0 and g(0) != 0
+        let swap = negative_mask(-delta) & nonzero_mask(g[0] as i64);
+        delta ^= swap & (delta ^ -delta);
+        delta += 1;
+        let swap = swap as u64;
+        for i in 0..=n {
+            let t = swap & (f[i] ^ g[i]);
+            f[i] ^= t;
+            g[i] ^= t;
+            let t = swap & (v[i] ^ r[i]);
+            v[i] ^= t;
+            r[i] ^= t;
+        }
+
+        // g <-- f(0)*g - g(0)*f and r <-- f(0)*r - g(0)*v, which cancels the constant term of g. Both products are
+        // below m^2 < 2^62, so their sum can't overflow an i64.
+        let (f0, neg_g0) = (f[0], m - g[0]);
+        for i in 0..=n {
+            g[i] = modulus.reduce((f0 * g[i] + neg_g0 * f[i]) as i64) as u64;
+            r[i] = modulus.reduce((f0 * r[i] + neg_g0 * v[i]) as i64) as u64;
+        }
+
+        // g(x) <-- g(x)/x
+        g.copy_within(1..=n, 0);
+        g[n] = 0;
+    }
+
+    if delta != 0 {
+        return None;
+    }
+    let scale = modulus.pow(f[0] as i64, m - 2);
+    Some(
+        (0..n)
+            .map(|i| modulus.reduce(scale * v[n - 1 - i] as i64) as u64)
+            .collect(),
+    )
+}
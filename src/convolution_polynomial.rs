@@ -1,3 +1,7 @@
+use crate::constant_time::{self, inverse_mod_prime};
+use crate::error::NtruError;
+use crate::multiplication::{cyclic_product, pow_mod, MulBackend};
+use crate::poly_ring::ReductionPoly;
 use rand::prelude::*;
 use std::cmp::max;
 use std::fmt;
@@ -6,12 +10,22 @@ use std::fmt;
 
 /// Generates a random ternary convolution polynomial of degree less than `n` with `num_ones` 1s and `num_neg_ones`
 /// -1s. The remaining coefficients are 0. The polynomial can be viewed as an element of the ring Z\[x\]/(x^n - 1).
-pub fn ternary_polynomial(n: usize, num_ones: usize, num_neg_ones: usize) -> ConvPoly {
-    assert!(
-        num_ones + num_neg_ones <= n,
-        "Number of 1s and -1s should be <= n (the number of terms in the polynomial)"
-    );
-    assert!(n > 0, "Polynomial degree should be greater than 0");
+/// Returns an error if `n` is 0 or there are more 1s and -1s than coefficients.
+pub fn ternary_polynomial(
+    n: usize,
+    num_ones: usize,
+    num_neg_ones: usize,
+) -> Result<ConvPoly, NtruError> {
+    if n == 0 {
+        return Err(NtruError::InvalidParameters(
+            "polynomial degree should be greater than 0",
+        ));
+    }
+    if num_ones.saturating_add(num_neg_ones) > n {
+        return Err(NtruError::InvalidParameters(
+            "number of 1s and -1s should be <= n (the number of terms in the polynomial)",
+        ));
+    }
 
     let mut poly = ConvPoly { coeffs: vec![0; n] };
     let mut rng = rand::thread_rng();
@@ -19,16 +33,16 @@ pub fn ternary_polynomial(n: usize, num_ones: usize, num_neg_ones: usize) -> Con
     rand_indices.shuffle(&mut rng);
 
     // Set the first `num_ones` random indices to 1
-    for i in 0..num_ones {
-        poly.coeffs[rand_indices[i]] = 1;
+    for &i in &rand_indices[..num_ones] {
+        poly.coeffs[i] = 1;
     }
 
     // Set the next `num_neg_ones` random indices to -1
-    for i in num_ones..num_ones + num_neg_ones {
-        poly.coeffs[rand_indices[i]] = -1;
+    for &i in &rand_indices[num_ones..num_ones + num_neg_ones] {
+        poly.coeffs[i] = -1;
     }
 
-    poly.trim()
+    Ok(poly.trim())
 }
 
 // CONVOLUTION POLYNOMIALS
@@ -90,7 +104,7 @@ impl ConvPoly {
 
     /// Returns the leading coefficient of the polynomial (i.e. the coefficient of x^degree)
     pub fn lc(&self) -> i32 {
-        self.coeffs[self.deg()]
+        self.coeffs.get(self.deg()).copied().unwrap_or(0)
     }
 
     /// Removes trailing zero coefficients from the polynomial.
@@ -101,105 +115,288 @@ impl ConvPoly {
     }
 
     /// Applies the modulus operation to each coefficient of the polynomial and returns the result,
-    /// which lies in the ring (Z/mZ)\[x\]/(x^N - 1). The modulus `m` must be a positive integer.
-    pub fn modulo(&self, m: i32) -> ConvPoly {
-        assert!(m > 0, "Modulus `m` must be a positive integer");
+    /// which lies in the ring (Z/mZ)\[x\]/(x^N - 1). Returns an error if the modulus `m` is not a positive integer.
+    pub fn modulo(&self, m: i32) -> Result<ConvPoly, NtruError> {
+        check_modulus(m)?;
 
         let result = ConvPoly {
             coeffs: self.coeffs.iter().map(|x| x.rem_euclid(m)).collect(),
         };
 
-        result.trim()
+        Ok(result.trim())
     }
 
     /// Lifts the polynomial out of the ring (Z/mZ)\[x\]/(x^N - 1) and into the ring Z\[x\]/(x^N - 1)
     /// by center-lifting each coefficient from \[0, m) --> (-m/2, m/2\]. The result is a polynomial
-    /// with the property p(x) ≡ p(x).center_lift(m) (mod m).
-    pub fn center_lift(&self, m: i32) -> ConvPoly {
+    /// with the property p(x) ≡ p(x).center_lift(m) (mod m). Returns an error if the modulus `m` is not a positive
+    /// integer.
+    pub fn center_lift(&self, m: i32) -> Result<ConvPoly, NtruError> {
         let result = ConvPoly {
-            coeffs: self.coeffs.iter().map(|x| center_lift(*x, m)).collect(),
+            coeffs: self
+                .coeffs
+                .iter()
+                .map(|x| center_lift(*x, m))
+                .collect::<Result<_, _>>()?,
         };
 
-        result.trim()
+        Ok(result.trim())
     }
 
-    /// Adds another polynomial to this one by adding the corresponding coefficients.
+    /// Adds another polynomial to this one by adding the corresponding coefficients. Coefficients that don't fit in an
+    /// i32 wrap around; see [`ConvPoly::checked_add`].
     pub fn add(&self, other: &ConvPoly) -> ConvPoly {
-        let max_len = max(self.coeffs.len(), other.coeffs.len());
-        let mut result = ConvPoly {
-            coeffs: Vec::with_capacity(max_len),
-        };
+        ConvPoly {
+            coeffs: self.zip_coeffs(other, i32::wrapping_add).collect(),
+        }
+        .trim()
+    }
 
-        for i in 0..max_len {
-            let a = self.coeffs.get(i).copied().unwrap_or(0);
-            let b = other.coeffs.get(i).copied().unwrap_or(0);
-            result.coeffs.push(a + b);
+    /// Subtracts another polynomial from this one by subtracting the corresponding coefficients. Coefficients that
+    /// don't fit in an i32 wrap around; see [`ConvPoly::checked_sub`].
+    pub fn sub(&self, other: &ConvPoly) -> ConvPoly {
+        ConvPoly {
+            coeffs: self.zip_coeffs(other, i32::wrapping_sub).collect(),
         }
+        .trim()
+    }
 
-        result.trim()
+    /// Adds another polynomial to this one. Returns an error if any coefficient of the sum doesn't fit in an i32.
+    pub fn checked_add(&self, other: &ConvPoly) -> Result<ConvPoly, NtruError> {
+        let coeffs = self
+            .zip_coeffs(other, i32::checked_add)
+            .collect::<Option<_>>()
+            .ok_or(NtruError::Overflow)?;
+        Ok(ConvPoly { coeffs }.trim())
     }
 
-    /// Subtracts another polynomial from this one by subtracting the corresponding coefficients.
-    pub fn sub(&self, other: &ConvPoly) -> ConvPoly {
-        let max_len = max(self.coeffs.len(), other.coeffs.len());
-        let mut result = ConvPoly {
-            coeffs: Vec::with_capacity(max_len),
-        };
+    /// Subtracts another polynomial from this one. Returns an error if any coefficient of the difference doesn't fit
+    /// in an i32.
+    pub fn checked_sub(&self, other: &ConvPoly) -> Result<ConvPoly, NtruError> {
+        let coeffs = self
+            .zip_coeffs(other, i32::checked_sub)
+            .collect::<Option<_>>()
+            .ok_or(NtruError::Overflow)?;
+        Ok(ConvPoly { coeffs }.trim())
+    }
 
-        for i in 0..max_len {
+    /// Applies an operation to each pair of corresponding coefficients, treating missing coefficients as 0
+    fn zip_coeffs<'a, T>(
+        &'a self,
+        other: &'a ConvPoly,
+        op: impl Fn(i32, i32) -> T + 'a,
+    ) -> impl Iterator<Item = T> + 'a {
+        (0..max(self.coeffs.len(), other.coeffs.len())).map(move |i| {
             let a = self.coeffs.get(i).copied().unwrap_or(0);
             let b = other.coeffs.get(i).copied().unwrap_or(0);
-            result.coeffs.push(a - b);
+            op(a, b)
+        })
+    }
+
+    /// Returns the product of this polynomial with another polynomial in the ring Z\[x\]/(x^n - 1), using whichever
+    /// multiplication backend is fastest for the size of the operands.
+    pub fn mul(&self, other: &ConvPoly, n: usize) -> ConvPoly {
+        self.mul_with(other, n, MulBackend::Auto)
+    }
+
+    /// Returns the product of this polynomial with another polynomial in the ring Z\[x\]/(x^n - 1), using the given
+    /// multiplication backend. Every backend gives the same result; coefficients that don't fit in an i32 wrap around.
+    pub fn mul_with(&self, other: &ConvPoly, n: usize, backend: MulBackend) -> ConvPoly {
+        ConvPoly {
+            coeffs: self
+                .exact_product(other, n, backend)
+                .into_iter()
+                .map(|c| c as i32)
+                .collect(),
         }
+        .trim()
+    }
 
-        result.trim()
+    /// Returns the product of this polynomial with another polynomial in the ring Z\[x\]/(x^n - 1). Returns an error
+    /// if any coefficient of the product doesn't fit in an i32.
+    pub fn checked_mul(&self, other: &ConvPoly, n: usize) -> Result<ConvPoly, NtruError> {
+        let coeffs = self
+            .exact_product(other, n, MulBackend::Auto)
+            .into_iter()
+            .map(|c| i32::try_from(c).map_err(|_| NtruError::Overflow))
+            .collect::<Result<_, _>>()?;
+        Ok(ConvPoly { coeffs }.trim())
     }
 
-    /// Returns the product of this polynomial with another polynomial in the ring Z\[x\]/(x^n - 1).
-    pub fn mul(&self, other: &ConvPoly, n: usize) -> ConvPoly {
+    /// Returns the product of this polynomial with another polynomial in the ring (Z/mZ)\[x\]/(x^n - 1), with
+    /// coefficients in \[0, m). The product is accumulated exactly before it's reduced, so it's correct for any
+    /// coefficients and modulus. Returns an error if the modulus `m` is not a positive integer.
+    pub fn mul_mod(&self, other: &ConvPoly, n: usize, m: i32) -> Result<ConvPoly, NtruError> {
+        check_modulus(m)?;
+        let result = ConvPoly {
+            coeffs: self
+                .exact_product(other, n, MulBackend::Auto)
+                .into_iter()
+                .map(|c| c.rem_euclid(m as i128) as i32)
+                .collect(),
+        };
+        Ok(result.trim())
+    }
+
+    /// Returns the coefficients of the product with another polynomial in Z\[x\]/(x^n - 1), computed without
+    /// overflow using the given backend
+    fn exact_product(&self, other: &ConvPoly, n: usize, backend: MulBackend) -> Vec<i128> {
         if self.is_zero() || other.is_zero() {
-            return ConvPoly::constant(0);
+            return vec![0];
+        }
+        let a = &self.coeffs[..=self.deg()];
+        let b = &other.coeffs[..=other.deg()];
+        cyclic_product(a, b, n, backend)
+    }
+
+    /// Returns the product x^k * f(x) of this polynomial f(x) with a power of x in the ring Z\[x\]/(x^n - 1), which
+    /// cyclically rotates its coefficients k places up (or down, for a negative k). Returns an error if n is 0.
+    pub fn rotate(&self, k: isize, n: usize) -> Result<ConvPoly, NtruError> {
+        check_degree(n)?;
+        let shift = k.rem_euclid(n as isize) as usize;
+        Ok(self.permute(n, |i| (i + shift) % n))
+    }
+
+    /// Returns f(x^k) for this polynomial f(x) in the ring Z\[x\]/(x^n - 1). For k coprime to n, x --> x^k is an
+    /// automorphism of the ring, which permutes the coefficients of f(x) without changing their values. Returns an
+    /// error if n is 0 or k isn't coprime to n.
+    pub fn automorphism(&self, k: usize, n: usize) -> Result<ConvPoly, NtruError> {
+        check_degree(n)?;
+        let k = k % n;
+        let coprime = match (i32::try_from(k), i32::try_from(n)) {
+            (Ok(k), Ok(n)) => gcd(k, n) == Ok(1),
+            _ => false,
+        };
+        if !coprime {
+            return Err(NtruError::InvalidParameters(
+                "automorphism exponent must be coprime to the degree",
+            ));
         }
-        let mut result = ConvPoly { coeffs: vec![0; n] };
+        Ok(self.permute(n, |i| i * k % n))
+    }
 
-        for i in 0..=self.deg() {
-            for j in 0..=other.deg() {
-                result.coeffs[(i + j) % n] += self.coeffs[i] * other.coeffs[j];
+    /// Returns the conjugate f(x^-1) = f(x^(n - 1)) of this polynomial f(x) in the ring Z\[x\]/(x^n - 1), which
+    /// reverses its coefficients other than the constant term. Returns an error if n is 0.
+    pub fn conjugate(&self, n: usize) -> Result<ConvPoly, NtruError> {
+        check_degree(n)?;
+        Ok(self.permute(n, |i| (n - i) % n))
+    }
+
+    /// Moves the coefficient of x^i to x^index(i) for each i in \[0, n), after folding coefficients of degree n and
+    /// above into place. Coefficients that don't fit in an i32 wrap around.
+    fn permute(&self, n: usize, index: impl Fn(usize) -> usize) -> ConvPoly {
+        let mut coeffs = vec![0i32; n];
+        for (i, &c) in self.coeffs.iter().enumerate() {
+            let j = index(i % n);
+            coeffs[j] = coeffs[j].wrapping_add(c);
+        }
+        ConvPoly { coeffs }.trim()
+    }
+
+    /// Returns this polynomial raised to the power `e` within the ring (Z/mZ)\[x\]/(x^n - 1), with coefficients in
+    /// \[0, m), by repeated squaring. Returns an error if the modulus `m` is not a positive integer or n is 0.
+    pub fn pow_mod(&self, e: u64, n: usize, m: i32) -> Result<ConvPoly, NtruError> {
+        check_degree(n)?;
+        let mut base = self.modulo(m)?;
+        let mut result = ConvPoly::constant(1).modulo(m)?;
+        let mut e = e;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.mul_mod(&base, n, m)?;
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.mul_mod(&base, n, m)?;
             }
         }
+        Ok(result)
+    }
+
+    /// Evaluates the polynomial at x = `a` modulo m using Horner's rule, and returns the result within \[0, m). The
+    /// value is only well defined on Z\[x\]/(x^n - 1) (i.e. independent of the representative) when a^n ≡ 1 (mod m).
+    /// Returns an error if the modulus `m` is not a positive integer.
+    pub fn eval_mod(&self, a: i32, m: i32) -> Result<i32, NtruError> {
+        check_modulus(m)?;
+        let (a, m) = (a.rem_euclid(m) as i64, m as i64);
+        let value = self
+            .coeffs
+            .iter()
+            .rev()
+            .fold(0, |acc, &c| (acc * a + c as i64).rem_euclid(m));
+        Ok(value as i32)
+    }
 
-        result.trim()
+    /// Returns the number of non-zero coefficients of the polynomial (its Hamming weight)
+    pub fn weight(&self) -> usize {
+        self.coeffs.iter().filter(|&&c| c != 0).count()
+    }
+
+    /// Returns the L1 norm of the polynomial, the sum of the absolute values of its coefficients
+    pub fn l1_norm(&self) -> u64 {
+        self.coeffs.iter().map(|&c| c.unsigned_abs() as u64).sum()
+    }
+
+    /// Returns the L2 norm of the polynomial, the square root of the sum of the squares of its coefficients
+    pub fn l2_norm(&self) -> f64 {
+        let squares: u128 = self
+            .coeffs
+            .iter()
+            .map(|&c| (c as i128 * c as i128) as u128)
+            .sum();
+        (squares as f64).sqrt()
+    }
+
+    /// Returns the infinity norm of the polynomial, the largest absolute value of its coefficients
+    pub fn linf_norm(&self) -> u32 {
+        self.coeffs
+            .iter()
+            .map(|&c| c.unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the centered norm of this polynomial a(x) in Z\[x\]/(x^n - 1) used throughout the NTRU literature, the
+    /// L2 norm of a(x) - μ(1 + x + ... + x^(n - 1)) where μ is the mean of its n coefficients. It's the smallest L2
+    /// norm of a(x) plus any multiple of 1 + x + ... + x^(n - 1), and equals sqrt(d1 + d2 - (d1 - d2)^2 / n) for
+    /// a(x) in T(d1, d2). Returns an error if n is 0.
+    pub fn centered_norm(&self, n: usize) -> Result<f64, NtruError> {
+        check_degree(n)?;
+        let mut folded = vec![0i128; n];
+        for (i, &c) in self.coeffs.iter().enumerate() {
+            folded[i % n] += c as i128;
+        }
+        let sum: i128 = folded.iter().sum();
+        let squares: i128 = folded.iter().map(|c| c * c).sum();
+        let variance = squares as f64 - (sum as f64) * (sum as f64) / n as f64;
+        Ok(variance.max(0.0).sqrt())
     }
 
     /// Divides the polynomial by another polynomial and returns the quotient and remainder. The division is
     /// treated as though it is happening within the polynomial ring (Z/mZ)\[x\]/(x^n-1). If the leading coefficient
     /// of the divisor is not a unit in the ring (Z/mZ), then the division is not possible and an error is returned.
+    /// An error is also returned when dividing by the zero polynomial.
     pub fn div_mod(
         &self,
         divisor: &ConvPoly,
         m: i32,
         n: usize,
-    ) -> Result<(ConvPoly, ConvPoly), String> {
-        assert!(
-            !divisor.is_zero(),
-            "Division by zero polynomial not permitted"
-        );
+    ) -> Result<(ConvPoly, ConvPoly), NtruError> {
+        check_modulus(m)?;
+        if divisor.is_zero() {
+            return Err(NtruError::ZeroDivisor);
+        }
 
         // Initialize the dividend and quotient; multiplication ensures exponents are considered mod n
         let mut remainder = self.clone().mul(&ConvPoly::constant(1), n);
         let mut quotient = ConvPoly::constant(0);
 
-        // Check whether the given divisor is valid by attempting to compute the multiplicative inverse of its leading coefficient
-        let inverse_divisor_lc = if let Ok(inverse) = inverse(divisor.lc(), m) {
-            inverse
-        } else {
-            return Err("Invalid divisor polynomial; no multiplicative inverse for its leading coefficient (mod m)".to_string());
-        };
+        // Check whether the given divisor is valid by attempting to compute the multiplicative inverse of its leading
+        // coefficient; division isn't possible without one
+        let inverse_divisor_lc = inverse(divisor.lc(), m)?;
 
         while remainder.deg() >= divisor.deg() && !remainder.is_zero() {
             // Construct the term c * x^d
             let d = remainder.deg() - divisor.deg();
-            let c = (remainder.lc() * inverse_divisor_lc).rem_euclid(m);
+            let c = (remainder.lc() as i64 * inverse_divisor_lc as i64).rem_euclid(m as i64) as i32;
             let term = ConvPoly {
                 coeffs: {
                     let mut coeffs = vec![0; d + 1];
@@ -208,20 +405,22 @@ impl ConvPoly {
                 },
             };
             // Add the term to the quotient
-            quotient = quotient.add(&term).modulo(m);
+            quotient = quotient.add(&term).modulo(m)?;
             // Subtract the term * divisor from the dividend
-            remainder = remainder.sub(&divisor.clone().mul(&term, n)).modulo(m);
+            remainder = remainder.sub(&divisor.mul_mod(&term, n, m)?).modulo(m)?;
         }
 
         Ok((quotient, remainder))
     }
 
-    pub fn gcd(a: &ConvPoly, b: &ConvPoly, m: i32, n: usize) -> Result<ConvPoly, String> {
-        assert!(
-            !a.is_zero() || !b.is_zero(),
-            "At least one of the polynomials must be non-zero"
-        );
-        assert!(m > 0, "Modulus `m` must be a positive integer");
+    /// The Euclidean Algorithm for polynomials. Returns the gcd of a(x) and b(x) within the ring (Z/mZ)\[x\]/(x^n - 1),
+    /// normalized to be monic when possible. Returns an error if both polynomials are zero or if division fails at
+    /// any point (which occurs when the leading coefficient of the divisor isn't a unit in the ring Z/mZ).
+    pub fn gcd(a: &ConvPoly, b: &ConvPoly, m: i32, n: usize) -> Result<ConvPoly, NtruError> {
+        check_modulus(m)?;
+        if a.is_zero() && b.is_zero() {
+            return Err(NtruError::ZeroDivisor);
+        }
         // Let a(x) = b(x)q(x) + r(x) (polynomial division algo). This algorithm works because
         // gcd(a(x), b(x)) = gcd(b(x), r(x)) since if a number divides a(x) and b(x), then it divides
         // a(x) - b(x)q(x) = r(x). We can therefore keep taking the remainder and shift until r(x) is 0
@@ -236,7 +435,7 @@ impl ConvPoly {
         // Normalize the gcd by dividing by its leading coefficient, if possible
         if let Ok(inverse) = inverse(old_r.lc(), m) {
             let inverse_poly = ConvPoly::constant(inverse);
-            old_r = old_r.mul(&inverse_poly, n).modulo(m);
+            old_r = old_r.mul_mod(&inverse_poly, n, m)?;
         }
 
         Ok(old_r)
@@ -245,18 +444,17 @@ impl ConvPoly {
     /// The Extended Euclidean Algorithm for polynomials. Returns (gcd, s(x), t(x)) such that
     /// a(x)s(x) + b(x)t(x) = gcd(a(x), b(x)) within the ring (Z/mZ)\[x\]/(x^n - 1). Returns an error
     /// if division fails at any point (which occurs when the leading coefficient of the divisor isn't
-    /// a unit in the ring Z/mZ).
+    /// a unit in the ring Z/mZ) or if both polynomials are zero.
     pub fn extended_gcd(
         a: &ConvPoly,
         b: &ConvPoly,
         m: i32,
         n: usize,
-    ) -> Result<(ConvPoly, ConvPoly, ConvPoly), String> {
-        assert!(
-            !a.is_zero() || !b.is_zero(),
-            "At least one of the polynomials must be non-zero"
-        );
-        assert!(m > 0, "Modulus `m` must be a positive integer");
+    ) -> Result<(ConvPoly, ConvPoly, ConvPoly), NtruError> {
+        check_modulus(m)?;
+        if a.is_zero() && b.is_zero() {
+            return Err(NtruError::ZeroDivisor);
+        }
         // Initial state
         // a(x) = 1a(x) + 0b(x)  -->  old_r(x) = a(x)old_s(x) + b(x)old_t(x)  so old_r(x) is a linear combination of a(x),b(x)
         // b(x) = 0a(x) + 1b(x)  -->  r(x) = a(x)s(x) + b(x)t(x)              so r(x) is also a linear combination of a(x),b(x)
@@ -273,26 +471,67 @@ impl ConvPoly {
         while !r.is_zero() {
             let (q, new_r) = old_r.div_mod(&r, m, n)?;
             (old_r, r) = (r, new_r);
-            (old_s, s) = (s.clone(), old_s.sub(&s.mul(&q, n)).modulo(m));
-            (old_t, t) = (t.clone(), old_t.sub(&t.mul(&q, n)).modulo(m));
+            (old_s, s) = (s.clone(), old_s.sub(&s.mul_mod(&q, n, m)?).modulo(m)?);
+            (old_t, t) = (t.clone(), old_t.sub(&t.mul_mod(&q, n, m)?).modulo(m)?);
         }
 
         // Normalize the solution by dividing by the gcd's leading coefficient, if possible
         if let Ok(inverse) = inverse(old_r.lc(), m) {
             let inverse_poly = ConvPoly::constant(inverse);
-            old_r = old_r.mul(&inverse_poly, n).modulo(m);
-            old_s = old_s.mul(&inverse_poly, n).modulo(m);
-            old_t = old_t.mul(&inverse_poly, n).modulo(m);
+            old_r = old_r.mul_mod(&inverse_poly, n, m)?;
+            old_s = old_s.mul_mod(&inverse_poly, n, m)?;
+            old_t = old_t.mul_mod(&inverse_poly, n, m)?;
         }
 
         Ok((old_r, old_s, old_t))
     }
 
     /// Computes the inverse of this polynomial within the ring (Z/mZ)\[x\]/(x^n - 1) using
-    /// the Extended Euclidean Algorithm. Returns an error if the polynomial is not invertible.
-    pub fn inverse(&self, m: i32, n: usize) -> Result<ConvPoly, String> {
-        if self.is_zero() {
-            return Err("The inverse of the zero polynomial does not exist.".to_string());
+    /// the Extended Euclidean Algorithm. When m isn't prime, Z/mZ isn't a field: for a power p^k of a prime the
+    /// inverse is computed modulo p and lifted with [`ConvPoly::inverse_mod_prime_power`] instead, and for any other
+    /// m it's computed modulo each prime power factor and recombined with the Chinese remainder theorem. Returns an
+    /// error if the polynomial is not invertible.
+    pub fn inverse(&self, m: i32, n: usize) -> Result<ConvPoly, NtruError> {
+        match factorize(m)[..] {
+            [(p, k)] if k > 1 => Ok(self.inverse_mod_prime_power(p, k, n)?.trim()),
+            ref factors @ [_, _, ..] => self.crt_inverse(factors, n),
+            _ => self.euclidean_inverse(m, n),
+        }
+    }
+
+    /// Computes the inverse of this polynomial modulo each of the given prime powers p^k, which must be pairwise
+    /// coprime, and combines them into its inverse modulo their product
+    fn crt_inverse(&self, factors: &[(i32, u32)], n: usize) -> Result<ConvPoly, NtruError> {
+        let mut result = vec![0i64; n];
+        let mut modulus = 1i64;
+        for &(p, k) in factors {
+            let component = self.inverse_mod_prime_power(p, k, n)?;
+            let m = p.pow(k) as i64;
+            // x ≡ result (mod modulus) and x ≡ component (mod m) when x = result + modulus*t with
+            // t ≡ (component - result)/modulus (mod m)
+            let modulus_inv = inverse((modulus % m) as i32, m as i32)? as i64;
+            for (i, x) in result.iter_mut().enumerate() {
+                let c = component.coeffs.get(i).copied().unwrap_or(0) as i64;
+                let t = ((c - *x) % m * modulus_inv).rem_euclid(m);
+                *x += modulus * t;
+            }
+            modulus *= m;
+        }
+
+        let result = ConvPoly {
+            coeffs: result.into_iter().map(|c| c as i32).collect(),
+        };
+        Ok(result.trim())
+    }
+
+    /// Computes the inverse of this polynomial within the ring (Z/mZ)\[x\]/(x^n - 1) using the Extended Euclidean
+    /// Algorithm, which finds it whenever it exists for a prime m
+    fn euclidean_inverse(&self, m: i32, n: usize) -> Result<ConvPoly, NtruError> {
+        // Reduce first: division needs the leading coefficient of the remainder to be a unit, which an unreduced
+        // coefficient that's a multiple of m never is
+        let a = self.modulo(m)?;
+        if a.is_zero() {
+            return Err(NtruError::NotInvertible);
         }
 
         // Create the modulus polynomial x^n - 1
@@ -304,15 +543,304 @@ impl ConvPoly {
             },
         };
 
-        let (gcd, s, _) = ConvPoly::extended_gcd(&self, &mod_poly, m, n + 1)?;
+        let (gcd, s, _) = ConvPoly::extended_gcd(&a, &mod_poly, m, n + 1)?;
 
         if gcd != ConvPoly::constant(1) {
-            return Err("The polynomial is not invertible in the given ring.".to_string());
+            return Err(NtruError::NotInvertible);
         }
 
         Ok(s)
     }
 
+    /// Computes the inverse of this polynomial within the ring (Z/mZ)\[x\]/(x^n - 1) for a prime m, in a time that
+    /// depends only on n and m rather than on the polynomial, so that it can be used on secret keys. The result has
+    /// all n coefficients, and otherwise matches [`ConvPoly::inverse`]. Returns an error if the polynomial is not
+    /// invertible, if m isn't prime or if n is 0.
+    pub fn inverse_constant_time(&self, m: i32, n: usize) -> Result<ConvPoly, NtruError> {
+        if !is_prime(m) {
+            return Err(NtruError::InvalidParameters("modulus `m` must be prime"));
+        }
+        if n == 0 {
+            return Err(NtruError::InvalidParameters("N must be positive"));
+        }
+
+        // Fold the coefficients into x^n - 1 and reduce them, which only depends on how many there are
+        let a: Vec<u64> = constant_time::modulo(&self.fold(n), m)?
+            .into_iter()
+            .map(|c| c as u64)
+            .collect();
+
+        let mut modulus_poly = vec![0; n + 1];
+        (modulus_poly[0], modulus_poly[n]) = (m as u64 - 1, 1);
+
+        let inverse =
+            inverse_mod_prime(&a, &modulus_poly, m as u64).ok_or(NtruError::NotInvertible)?;
+        // Trimming the zeros at the top would take a time that depends on the inverse
+        Ok(ConvPoly {
+            coeffs: inverse.into_iter().map(|c| c as i32).collect(),
+        })
+    }
+
+    /// Computes the inverse of this polynomial within the ring (Z/2^kZ)\[x\]/(x^n - 1). See
+    /// [`ConvPoly::inverse_mod_prime_power`]. Returns an error if the polynomial is not invertible modulo 2 (and
+    /// therefore not modulo 2^k either), or if `k` doesn't lie within \[1, 30\].
+    pub fn inverse_mod_pow2(&self, k: u32, n: usize) -> Result<ConvPoly, NtruError> {
+        if !(1..=30).contains(&k) {
+            return Err(NtruError::InvalidParameters(
+                "exponent `k` must lie within [1, 30]",
+            ));
+        }
+        self.inverse_mod_prime_power(2, k, n)
+    }
+
+    /// Computes the inverse of this polynomial within the ring (Z/p^kZ)\[x\]/(x^n - 1) for a prime p. Since Z/p^kZ is
+    /// not a field, the polynomial is first inverted modulo p and the inverse b(x) is then Newton-lifted with
+    /// b(x) <-- b(x)(2 - a(x)b(x)), which doubles the power of p that b(x) is an inverse modulo at each step. Like
+    /// [`ConvPoly::inverse_constant_time`], every step takes a time that depends only on n, p and k, and the result
+    /// has all n coefficients within \[0, p^k). Returns an error if the polynomial is not invertible modulo p (and
+    /// therefore not modulo p^k either), if `p` isn't prime, or if p^k doesn't fit in an i32.
+    pub fn inverse_mod_prime_power(&self, p: i32, k: u32, n: usize) -> Result<ConvPoly, NtruError> {
+        if !is_prime(p) {
+            return Err(NtruError::InvalidParameters("`p` must be prime"));
+        }
+        if k == 0 || p.checked_pow(k).is_none() {
+            return Err(NtruError::InvalidParameters(
+                "exponent `k` must be positive, with p^k fitting in an i32",
+            ));
+        }
+
+        let mut inverse = self.inverse_constant_time(p, n)?.coeffs;
+        let (a, reduction) = (self.fold(n), ReductionPoly::Cyclic(n));
+        let mut e = 1;
+
+        while e < k {
+            // The inverse is currently correct modulo p^e; lift it to modulo p^min(2e, k)
+            e = (2 * e).min(k);
+            let m = p.pow(e);
+            // f(x)*inverse(x) ≡ 1 (mod p^e), so 2 - f(x)*inverse(x) corrects the inverse modulo p^2e
+            let product = constant_time::product(&a, &inverse, &reduction, m)?;
+            let correction: Vec<i64> = product
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| 2 * (i == 0) as i64 - c as i64)
+                .collect();
+            inverse = constant_time::product(&inverse, &correction, &reduction, m)?;
+        }
+
+        Ok(ConvPoly { coeffs: inverse })
+    }
+
+    /// Returns the n coefficients of this polynomial folded into x^n - 1, in a time that only depends on how many
+    /// coefficients there are
+    fn fold(&self, n: usize) -> Vec<i64> {
+        let mut folded = vec![0i64; n];
+        for (i, &coeff) in self.coeffs.iter().enumerate() {
+            folded[i % n] += coeff as i64;
+        }
+        folded
+    }
+
+    /// Returns whether this polynomial is invertible within the ring (Z/mZ)\[x\]/(x^n - 1), which is the case exactly
+    /// when its resultant with x^n - 1 is non-zero modulo every prime dividing m. This is much cheaper than computing
+    /// the inverse. Returns false if m is less than 2 or n is 0.
+    pub fn is_invertible(&self, m: i32, n: usize) -> bool {
+        let reduction = ReductionPoly::Cyclic(n);
+        m >= 2
+            && factorize(m)
+                .into_iter()
+                .all(|(p, _)| matches!(self.resultant_mod(&reduction, p), Ok(r) if r != 0))
+    }
+
+    /// Explains why this polynomial isn't invertible within the ring (Z/mZ)\[x\]/(x^n - 1), as a prime r dividing m
+    /// and the smallest divisor d of n for which it shares a factor with the cyclotomic polynomial Φ_d(x) modulo r.
+    /// Returns None if the polynomial is invertible, or if m is less than 2 or n is 0.
+    pub fn shared_factor(&self, m: i32, n: usize) -> Option<(i32, usize)> {
+        if m < 2 {
+            return None;
+        }
+        let factors = ReductionPoly::cyclotomic_factors(n);
+        factorize(m).into_iter().find_map(|(r, _)| {
+            (1..=n)
+                .filter(|&d| n.is_multiple_of(d))
+                .zip(&factors)
+                .find(|(_, factor)| matches!(self.resultant_mod(factor, r), Ok(0)))
+                .map(|(d, _)| (r, d))
+        })
+    }
+
+    /// Returns the resultant Res(f, a) over Z of the monic reduction polynomial f(x) and this polynomial a(x), which
+    /// is the product of a(x) over the roots of f(x). It's computed modulo enough primes to recover it from a bound on
+    /// its size. Returns an error if the reduction polynomial isn't monic with positive degree, or if that bound is too
+    /// large for i128 arithmetic. That limits it to small polynomials: a ternary polynomial with x^N - 1 only works up
+    /// to N of about 35, far below any practical parameter set, for which resultant_mod still works.
+    pub fn resultant(&self, reduction: &ReductionPoly) -> Result<i128, NtruError> {
+        reduction.check()?;
+        if self.is_zero() {
+            return Ok(0);
+        }
+
+        // Hadamard's bound on the determinant of the Sylvester matrix: |Res(f, a)| <= |f|^deg(a) * |a|^deg(f)
+        let log_norm = |coeffs: &[i32]| {
+            (coeffs.iter().map(|&c| c as f64 * c as f64).sum::<f64>()).log2() / 2.0
+        };
+        let f = reduction.coeffs();
+        let log_bound =
+            self.deg() as f64 * log_norm(&f) + reduction.degree() as f64 * log_norm(&self.coeffs);
+
+        // Chinese remaindering over the primes below 2^31, until their product exceeds twice the bound (with a bit to
+        // spare for rounding). That product has to fit in an i128 too.
+        if log_bound + 2.0 + 31.0 >= 127.0 {
+            return Err(NtruError::Overflow);
+        }
+        let (mut result, mut modulus) = (0i128, 1i128);
+        let mut p = i32::MAX;
+        while (modulus as f64).log2() <= log_bound + 2.0 {
+            if is_prime(p) {
+                let r = self.resultant_mod(reduction, p)? as i128;
+                let p_wide = p as i128;
+                let modulus_inv = inverse((modulus % p_wide) as i32, p)? as i128;
+                let t = ((r - result % p_wide) * modulus_inv).rem_euclid(p_wide);
+                result += modulus * t;
+                modulus *= p_wide;
+            }
+            p -= 2;
+        }
+
+        Ok(if result > modulus / 2 {
+            result - modulus
+        } else {
+            result
+        })
+    }
+
+    /// Returns the resultant Res(f, a) modulo m of the monic reduction polynomial f(x) and this polynomial a(x),
+    /// within \[0, m). Modulo a prime it's zero exactly when f(x) and a(x) share a factor, i.e. when a(x) isn't
+    /// invertible within (Z/pZ)\[x\]/(f(x)). It's computed modulo every prime power dividing m and combined with the
+    /// Chinese remainder theorem. Returns an error if m is less than 2 or the reduction polynomial isn't monic with
+    /// positive degree.
+    pub fn resultant_mod(&self, reduction: &ReductionPoly, m: i32) -> Result<i32, NtruError> {
+        if m < 2 {
+            return Err(NtruError::InvalidParameters(
+                "modulus `m` must be at least 2",
+            ));
+        }
+        reduction.check()?;
+
+        let (mut result, mut modulus) = (0i64, 1i64);
+        for (p, k) in factorize(m) {
+            let pk = p.pow(k);
+            let r = match k {
+                1 => self.resultant_mod_prime(reduction, p),
+                _ => self.resultant_mod_prime_power(reduction, p, k),
+            } as i64;
+            let modulus_inv = inverse((modulus % pk as i64) as i32, pk)? as i64;
+            let t = ((r - result) % pk as i64 * modulus_inv).rem_euclid(pk as i64);
+            result += modulus * t;
+            modulus *= pk as i64;
+        }
+        Ok(result as i32)
+    }
+
+    /// Returns Res(f, a) modulo a prime p, within \[0, p), for a reduction polynomial that has been checked
+    fn resultant_mod_prime(&self, reduction: &ReductionPoly, p: i32) -> i32 {
+        let p = p as i64;
+        let reduce = |coeffs: &[i32]| -> Vec<i64> {
+            coeffs.iter().map(|&c| (c as i64).rem_euclid(p)).collect()
+        };
+
+        // Since f(x) is monic, Res(f, a) = Res(f, a mod f). Then Res(u, v) = (-1)^(deg(u)deg(v)) * lc(v)^(deg(u) -
+        // deg(r)) * Res(v, r) for the remainder r(x) of u(x) divided by v(x), down to Res(u, c) = c^deg(u) for a
+        // constant c.
+        let mut u = reduce(&reduction.coeffs());
+        let (_, mut v) = div_rem_mod_prime(reduce(&self.coeffs), &u, p);
+        let mut result = 1;
+        loop {
+            let (du, dv) = (u.len() - 1, v.len().saturating_sub(1));
+            match v[..] {
+                [] => return 0,
+                [c] => return (result * pow_mod(c as u64, du as u64, p as u64) as i64 % p) as i32,
+                _ => {}
+            }
+            let (_, r) = div_rem_mod_prime(u, &v, p);
+            if r.is_empty() {
+                return 0;
+            }
+            if du * dv % 2 == 1 {
+                result = (p - result) % p;
+            }
+            let lc = *v.last().unwrap() as u64;
+            result = result * pow_mod(lc, (du - (r.len() - 1)) as u64, p as u64) as i64 % p;
+            (u, v) = (v, r);
+        }
+    }
+
+    /// Returns Res(f, a) modulo a prime power p^k, within \[0, p^k), for a reduction polynomial that has been checked
+    fn resultant_mod_prime_power(&self, reduction: &ReductionPoly, p: i32, k: u32) -> i32 {
+        let (p, pk) = (p as i64, p.pow(k) as i64);
+        let f: Vec<i64> = reduction.coeffs().iter().map(|&c| c as i64).collect();
+        let d = f.len() - 1;
+
+        // Since f(x) is monic, Res(f, a) is the determinant of multiplication by a(x) within Z[x]/(f(x)), whose rows
+        // are x^i * a(x) mod f(x) for i < deg(f)
+        let mut row: Vec<i64> = self
+            .coeffs
+            .iter()
+            .map(|&c| (c as i64).rem_euclid(pk))
+            .collect();
+        for i in (d..row.len()).rev() {
+            let c = row[i];
+            for (coeff, &x) in row[i - d..=i].iter_mut().zip(&f) {
+                *coeff = (*coeff - c * x).rem_euclid(pk);
+            }
+        }
+        row.resize(d, 0);
+        let mut matrix = Vec::with_capacity(d);
+        for _ in 0..d {
+            matrix.push(row.clone());
+            let top = row.pop().unwrap();
+            row.insert(0, 0);
+            for (coeff, &x) in row.iter_mut().zip(&f) {
+                *coeff = (*coeff - top * x).rem_euclid(pk);
+            }
+        }
+
+        // Z/p^kZ isn't a field, but every entry of a column is a multiple of the one with the fewest factors of p, so
+        // eliminating with that one as the pivot only needs the inverse of a unit
+        let valuation = |mut x: i64| {
+            let mut v = 0;
+            while x % p == 0 {
+                x /= p;
+                v += 1;
+            }
+            v
+        };
+        let phi = (pk / p * (p - 1)) as u64;
+        let mut det = 1;
+        for col in 0..d {
+            let Some(pivot) = (col..d)
+                .filter(|&r| matrix[r][col] != 0)
+                .min_by_key(|&r| valuation(matrix[r][col]))
+            else {
+                return 0;
+            };
+            if pivot != col {
+                matrix.swap(pivot, col);
+                det = (pk - det) % pk;
+            }
+            det = det * matrix[col][col] % pk;
+            let scale = p.pow(valuation(matrix[col][col]));
+            let unit_inv = pow_mod((matrix[col][col] / scale) as u64, phi - 1, pk as u64) as i64;
+            let pivot_row = matrix[col].clone();
+            for row in &mut matrix[col + 1..] {
+                let factor = row[col] / scale * unit_inv % pk;
+                for (coeff, &x) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *coeff = (*coeff - factor * x).rem_euclid(pk);
+                }
+            }
+        }
+        det as i32
+    }
+
     /// Serializes the convolution polynomial into a big-endian byte vector. Each coefficient
     /// is represented by 4 bytes
     pub fn to_be_bytes(&self) -> Vec<u8> {
@@ -324,39 +852,50 @@ impl ConvPoly {
     }
 
     /// Deserializes a byte vector into a convolution polynomial. The byte vector is assumed to be
-    /// in big-endian format with each coefficient represented by 4 bytes.
-    pub fn from_be_bytes(buf: &Vec<u8>) -> ConvPoly {
-        let mut coeffs = Vec::new();
+    /// in big-endian format with each coefficient represented by 4 bytes. Returns an error if the length of the byte
+    /// vector isn't a multiple of 4.
+    pub fn from_be_bytes(buf: &[u8]) -> Result<ConvPoly, NtruError> {
+        if !buf.len().is_multiple_of(size_of::<i32>()) {
+            return Err(NtruError::InvalidEncoding(
+                "polynomial byte length must be a multiple of 4",
+            ));
+        }
+
+        let mut coeffs = Vec::with_capacity(buf.len() / size_of::<i32>());
         for i in (0..buf.len()).step_by(size_of::<i32>()) {
             let coeff = i32::from_be_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
             coeffs.push(coeff);
         }
 
-        ConvPoly { coeffs }
+        Ok(ConvPoly { coeffs })
     }
 }
 
 // INTEGER ARITHMETIC
 
-/// The Euclidean Algorithm. Return the greatest common divisor and a and b.
-pub fn gcd(a: i32, b: i32) -> i32 {
-    assert!(a != 0 || b != 0, "At least one of a and b must be non-zero");
+/// The Euclidean Algorithm. Return the greatest common divisor and a and b. Returns an error if both are zero.
+pub fn gcd(a: i32, b: i32) -> Result<i32, NtruError> {
+    if a == 0 && b == 0 {
+        return Err(NtruError::ZeroDivisor);
+    }
     // Let a = bq + r (division algo). This algorithm works because gcd(a, b) = gcd(b, r) since
     // if a number divides a and b, then it divides a - bq = r. We can therefore
     // keep taking the remainder and shift until r is 0 (which is guaranteed to happen)
-    let (mut old_r, mut r) = (a.clone().abs(), b.clone().abs());
+    let (mut old_r, mut r) = (a.abs(), b.abs());
 
     while r != 0 {
         (old_r, r) = (r, old_r % r);
     }
 
-    old_r
+    Ok(old_r)
 }
 
 /// The Extended Euclidean Algorithm. Returns (gcd, x, y) such that ax + by = gcd(a, b).
-/// If negative inputs are provided, the algorithm will use their absolute values.
-pub fn extended_gcd(a: i32, b: i32) -> (i32, i32, i32) {
-    assert!(a != 0 || b != 0, "At least one of a and b must be non-zero");
+/// If negative inputs are provided, the algorithm will use their absolute values. Returns an error if both are zero.
+pub fn extended_gcd(a: i32, b: i32) -> Result<(i32, i32, i32), NtruError> {
+    if a == 0 && b == 0 {
+        return Err(NtruError::ZeroDivisor);
+    }
     // Initial state
     // a = 1a + 0b  -->  old_r = a(old_x) + b(old_y)  so `old_r` is a linear combination of a,b
     // b = 0a + 1b  -->  r = ax + by                  so `r` is also a linear combination of a,b
@@ -366,8 +905,8 @@ pub fn extended_gcd(a: i32, b: i32) -> (i32, i32, i32) {
     // a linear combination of a,b with new_x = old_x - xq and new_y = old_y - yq. By induction, we can
     // continue assigning new_r to r like this until r = 0 (which we know will happen by the standard
     // Euclidean Algorithm) and be left with Bézout coefficients.
-    let (mut old_r, mut old_x, mut old_y) = (a.clone().abs(), 1, 0);
-    let (mut r, mut x, mut y) = (b.clone().abs(), 0, 1);
+    let (mut old_r, mut old_x, mut old_y) = (a.abs(), 1, 0);
+    let (mut r, mut x, mut y) = (b.abs(), 0, 1);
 
     while r != 0 {
         let q = old_r / r;
@@ -376,39 +915,121 @@ pub fn extended_gcd(a: i32, b: i32) -> (i32, i32, i32) {
         (old_y, y) = (y, old_y - y * q);
     }
 
-    (old_r, old_x, old_y)
+    Ok((old_r, old_x, old_y))
+}
+
+/// Returns whether `a` is a prime number, using trial division.
+pub fn is_prime(a: i32) -> bool {
+    if a < 2 {
+        return false;
+    }
+
+    let mut i = 2;
+    while i <= a / i {
+        if a % i == 0 {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Returns the prime factorization of `m` as its distinct prime factors p in increasing order, each with its exponent
+/// k, using trial division. The factorization of an integer below 2 is empty.
+pub(crate) fn factorize(m: i32) -> Vec<(i32, u32)> {
+    let mut factors = Vec::new();
+    let mut rest = m;
+    let mut p = 2;
+    while p <= rest / p {
+        if rest % p == 0 {
+            let mut k = 0;
+            while rest % p == 0 {
+                rest /= p;
+                k += 1;
+            }
+            factors.push((p, k));
+        }
+        p += 1;
+    }
+    if rest > 1 {
+        factors.push((rest, 1));
+    }
+    factors
 }
 
 /// Returns the multiplicative inverse of `a` within the unit group (Z/mZ)*. Returns an error if no
-/// such inverse exists (i.e. if `a` is not relatively prime to `m`, and therefore not a member of the group).
-pub fn inverse(a: i32, m: i32) -> Result<i32, String> {
-    assert!(m > 0, "Modulus `m` must be a positive integer");
+/// such inverse exists (i.e. if `a` is not relatively prime to `m`, and therefore not a member of the group), or if
+/// the modulus `m` is not a positive integer.
+pub fn inverse(a: i32, m: i32) -> Result<i32, NtruError> {
+    check_modulus(m)?;
 
     if a == 0 {
-        return Err("The multiplicative inverse of 0 does not exist.".to_string());
+        return Err(NtruError::NotInvertible);
     }
 
-    let (gcd, x, _) = extended_gcd(a.rem_euclid(m), m);
+    // `a` only has a multiplicative inverse (mod m) if it is relatively prime to m
+    let (gcd, x, _) = extended_gcd(a.rem_euclid(m), m)?;
 
     if gcd != 1 {
-        return Err(
-            "`a` only has a multiplicative inverse (mod m) if it is relatively prime to m."
-                .to_string(),
-        );
+        return Err(NtruError::NotInvertible);
     }
 
     Ok(x.rem_euclid(m))
 }
 
 /// Lifts `a` out of the ring Z/mZ and into the ring Z by taking [0, m) --> (-m/2, m/2] with
-/// the property a ≡ center_lift(a, m) (mod m).
-pub fn center_lift(a: i32, m: i32) -> i32 {
-    assert!(m > 0, "Modulus `m` must be a positive integer");
+/// the property a ≡ center_lift(a, m) (mod m). Returns an error if the modulus `m` is not a positive integer.
+pub fn center_lift(a: i32, m: i32) -> Result<i32, NtruError> {
+    check_modulus(m)?;
 
     let a = a.rem_euclid(m);
     if a <= m / 2 {
-        a
+        Ok(a)
+    } else {
+        Ok(a - m)
+    }
+}
+
+/// Divides u(x) by v(x) modulo a prime p, given their coefficients reduced modulo p, and returns the quotient and
+/// remainder with trailing zeros removed. v(x) must have a non-zero leading coefficient.
+pub(crate) fn div_rem_mod_prime(mut u: Vec<i64>, v: &[i64], p: i64) -> (Vec<i64>, Vec<i64>) {
+    let trim = |coeffs: &mut Vec<i64>| {
+        while coeffs.last() == Some(&0) {
+            coeffs.pop();
+        }
+    };
+    trim(&mut u);
+    let dv = v.len() - 1;
+    let lc_inv = pow_mod(v[dv] as u64, p as u64 - 2, p as u64) as i64;
+    let mut quotient = vec![0; u.len().saturating_sub(dv)];
+    while u.len() > dv {
+        let d = u.len() - 1 - dv;
+        let c = u[u.len() - 1] * lc_inv % p;
+        quotient[d] = c;
+        for (coeff, &x) in u[d..].iter_mut().zip(v) {
+            *coeff = (*coeff - c * x).rem_euclid(p);
+        }
+        trim(&mut u);
+    }
+    trim(&mut quotient);
+    (quotient, u)
+}
+
+/// Returns an error if the modulus `m` is not a positive integer.
+fn check_modulus(m: i32) -> Result<(), NtruError> {
+    if m > 0 {
+        Ok(())
+    } else {
+        Err(NtruError::InvalidModulus(m))
+    }
+}
+
+/// Returns an error if the ring Z\[x\]/(x^n - 1) is degenerate, i.e. if n is 0.
+fn check_degree(n: usize) -> Result<(), NtruError> {
+    if n > 0 {
+        Ok(())
     } else {
-        a - m
+        Err(NtruError::InvalidParameters("N must be positive"))
     }
 }
@@ -0,0 +1,212 @@
+use crate::convolution_polynomial::{center_lift, ternary_polynomial, ConvPoly};
+use crate::error::NtruError;
+use crate::params::NtruParams;
+use rand::distributions::{Distribution, WeightedIndex};
+
+// Decryption computes a(x) ≡ e(x) * f(x) ≡ p*r(x)*g(x) + f(x)*m(x) (mod q) and center-lifts it into (-q/2, q/2].
+// Decryption only recovers m(x) if the center-lift returns the true integer coefficients of p*r(x)*g(x) + f(x)*m(x),
+// so a coefficient outside of that window is exactly a decryption failure.
+
+/// The distribution of message coefficients, given as the probability of each integer value a coefficient can take
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageDistribution {
+    /// The smallest value a coefficient can take
+    min: i32,
+    /// probs\[k\] is the probability that a coefficient equals min + k
+    probs: Vec<f64>,
+}
+
+impl MessageDistribution {
+    /// Constructs a message distribution where probs\[k\] is the probability that a coefficient equals min + k. The
+    /// probabilities are normalized so that they sum to 1. Returns an error if any probability is negative or none
+    /// of them are positive.
+    pub fn new(min: i32, probs: Vec<f64>) -> Result<MessageDistribution, NtruError> {
+        if !probs.iter().all(|&prob| prob >= 0.0 && prob.is_finite()) {
+            return Err(NtruError::InvalidParameters(
+                "probabilities must be finite and non-negative",
+            ));
+        }
+        let total: f64 = probs.iter().sum();
+        if total <= 0.0 {
+            return Err(NtruError::InvalidParameters(
+                "at least one probability must be positive",
+            ));
+        }
+
+        Ok(MessageDistribution {
+            min,
+            probs: probs.iter().map(|prob| prob / total).collect(),
+        })
+    }
+
+    /// The distribution where every coefficient is chosen uniformly from the center-lifted residues (-p/2, p/2].
+    /// Returns an error if the modulus `p` is not a positive integer.
+    pub fn uniform(p: i32) -> Result<MessageDistribution, NtruError> {
+        if p <= 0 {
+            return Err(NtruError::InvalidModulus(p));
+        }
+        MessageDistribution::new(p / 2 - p + 1, vec![1.0; p as usize])
+    }
+
+    /// The distribution over ternary coefficients where -1, 0 and 1 occur with the given probabilities. Returns an
+    /// error if the probabilities don't form a distribution.
+    pub fn ternary(
+        prob_neg_one: f64,
+        prob_zero: f64,
+        prob_one: f64,
+    ) -> Result<MessageDistribution, NtruError> {
+        MessageDistribution::new(-1, vec![prob_neg_one, prob_zero, prob_one])
+    }
+
+    /// Returns an iterator over every (value, probability) pair of the distribution
+    fn values(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(k, &prob)| (self.min + k as i32, prob))
+    }
+
+    /// Samples a random message polynomial with `n` coefficients drawn from the distribution
+    fn sample(&self, n: usize) -> ConvPoly {
+        let mut rng = rand::thread_rng();
+        let dist = WeightedIndex::new(&self.probs).expect("Probabilities are normalized");
+        ConvPoly {
+            coeffs: (0..n)
+                .map(|_| self.min + dist.sample(&mut rng) as i32)
+                .collect(),
+        }
+    }
+}
+
+/// An estimate of how likely decryption is to fail
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailureEstimate {
+    /// The probability that a single coefficient of p*r(x)*g(x) + f(x)*m(x) lies outside (-q/2, q/2]
+    pub per_coefficient: f64,
+    /// The probability that at least one of the N coefficients does, i.e. that a message fails to decrypt
+    pub per_message: f64,
+}
+
+/// Analytically estimates the decryption failure probability of the given parameter set and message distribution.
+/// Each coefficient of p*r(x)*g(x) + f(x)*m(x) is a sum of N terms p*r_j*g_(i-j) + f_j*m_(i-j); the terms are treated
+/// as independent, so the distribution of the coefficient is the N-fold convolution of the distribution of a single
+/// term. The per-message probability treats the N coefficients as independent as well. Returns an error if the
+/// parameter set is unusable.
+pub fn analytic_failure_probability(
+    params: &NtruParams,
+    msg_dist: &MessageDistribution,
+) -> Result<FailureEstimate, NtruError> {
+    params.ensure_usable()?;
+    let NtruParams { n, p, q, d } = *params;
+    let (n_f64, d_f64) = (n as f64, d as f64);
+
+    // Distribution of r_j*g_(i-j) over {-1, 0, 1}, where r(x), g(x) are in T(d, d)
+    let prob_rg = 2.0 * (d_f64 / n_f64).powi(2);
+    // Distribution of f_j over {-1, 0, 1}, where f(x) is in T(d + 1, d)
+    let (prob_f_neg_one, prob_f_one) = (d_f64 / n_f64, (d_f64 + 1.0) / n_f64);
+
+    // Distribution of a single term p*r_j*g_(i-j) + f_j*m_(i-j)
+    let max_msg = msg_dist.values().map(|(v, _)| v.abs()).max().unwrap_or(0);
+    let term_offset = p + max_msg;
+    let mut term = vec![0.0; 2 * term_offset as usize + 1];
+    for (rg, prob_rg) in [(-1, prob_rg), (0, 1.0 - 2.0 * prob_rg), (1, prob_rg)] {
+        for (f, prob_f) in [
+            (-1, prob_f_neg_one),
+            (0, 1.0 - prob_f_neg_one - prob_f_one),
+            (1, prob_f_one),
+        ] {
+            for (m, prob_m) in msg_dist.values() {
+                let value = p * rg + f * m;
+                term[(value + term_offset) as usize] += prob_rg * prob_f * prob_m;
+            }
+        }
+    }
+
+    // Distribution of a coefficient as the N-fold convolution of the term distribution. coeff[k] is the probability
+    // that the coefficient equals k - offset.
+    let mut coeff = vec![1.0];
+    for _ in 0..n {
+        let mut next = vec![0.0; coeff.len() + term.len() - 1];
+        for (i, &a) in coeff.iter().enumerate() {
+            if a == 0.0 {
+                continue;
+            }
+            for (j, &b) in term.iter().enumerate() {
+                next[i + j] += a * b;
+            }
+        }
+        coeff = next;
+    }
+    let offset = n as i64 * term_offset as i64;
+
+    // Sum the probabilities of every value outside of the center-lift window directly, which keeps tiny
+    // probabilities accurate. Since r(x)*g(x) has at most 2d nonzero terms and f(x)*m(x) at most 2d + 1, values
+    // beyond p*2d + (2d + 1)*max|m| can't occur and are an artifact of treating the terms as independent.
+    let (hi, lo) = (q as i64 / 2, q as i64 / 2 - q as i64 + 1);
+    let worst_case = p as i64 * 2 * d as i64 + (2 * d as i64 + 1) * max_msg as i64;
+    let per_coefficient: f64 = coeff
+        .iter()
+        .enumerate()
+        .filter(|&(k, _)| {
+            let value = k as i64 - offset;
+            (value < lo || value > hi) && value.abs() <= worst_case
+        })
+        .map(|(_, prob)| prob)
+        .sum();
+
+    Ok(FailureEstimate {
+        per_coefficient,
+        per_message: per_message_probability(per_coefficient, n),
+    })
+}
+
+/// Estimates the decryption failure probability of the given parameter set and message distribution by sampling
+/// `trials` random f(x), g(x), r(x) and m(x) and counting how often a coefficient of p*r(x)*g(x) + f(x)*m(x) falls
+/// outside (-q/2, q/2]. Failure probabilities much smaller than 1 / `trials` are estimated as 0. Returns an error if
+/// the parameter set is unusable or no trials are requested.
+pub fn monte_carlo_failure_probability(
+    params: &NtruParams,
+    msg_dist: &MessageDistribution,
+    trials: usize,
+) -> Result<FailureEstimate, NtruError> {
+    params.ensure_usable()?;
+    if trials == 0 {
+        return Err(NtruError::InvalidParameters(
+            "at least one trial is required",
+        ));
+    }
+    let NtruParams { n, p, q, d } = *params;
+    let p = ConvPoly::constant(p);
+
+    let (mut failed_coeffs, mut failed_msgs) = (0, 0);
+    for _ in 0..trials {
+        let f = ternary_polynomial(n, d + 1, d)?;
+        let g = ternary_polynomial(n, d, d)?;
+        let r = ternary_polynomial(n, d, d)?;
+        let m = msg_dist.sample(n);
+
+        // a(x) = p*r(x)*g(x) + f(x)*m(x) over Z, before any reduction modulo q
+        let a = p.mul(&r.mul(&g, n), n).add(&f.mul(&m, n));
+        let failures = a
+            .coeffs
+            .iter()
+            .filter(|&&c| center_lift(c, q) != Ok(c))
+            .count();
+
+        failed_coeffs += failures;
+        if failures > 0 {
+            failed_msgs += 1;
+        }
+    }
+
+    Ok(FailureEstimate {
+        per_coefficient: failed_coeffs as f64 / (trials * n) as f64,
+        per_message: failed_msgs as f64 / trials as f64,
+    })
+}
+
+/// Returns the probability that at least one of `n` independent coefficients fails, 1 - (1 - p)^n, computed so
+/// that it stays accurate for tiny p
+fn per_message_probability(per_coefficient: f64, n: usize) -> f64 {
+    -(n as f64 * (-per_coefficient).ln_1p()).exp_m1()
+}
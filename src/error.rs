@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// The errors that can occur within the NTRU encryption scheme and the polynomial arithmetic underlying it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NtruError {
+    /// The polynomial or integer has no multiplicative inverse in the ring it was inverted in
+    NotInvertible,
+    /// An operation required a non-zero divisor (or at least one non-zero operand) but was only given zeros
+    ZeroDivisor,
+    /// The given modulus is not a positive integer
+    InvalidModulus(i32),
+    /// The message is longer than the maximum number of bytes that fit in a single polynomial
+    MessageTooLong { len: usize, max: usize },
+    /// A byte buffer or polynomial does not represent a valid encoding of what it was decoded as
+    InvalidEncoding(&'static str),
+    /// A key, ciphertext or polynomial does not match the parameter set it is being used with
+    ParameterMismatch(&'static str),
+    /// The given parameters can't be used to construct what was asked for
+    InvalidParameters(&'static str),
+    /// Elements of two different polynomial rings were combined
+    RingMismatch,
+    /// No invertible private key was found among the candidates tried. `shared_factor` explains why the last one was
+    /// rejected, as a prime r dividing p or q and the divisor d of N for which it shared a factor with Φ_d(x) modulo r.
+    KeyGenerationFailed {
+        attempts: usize,
+        shared_factor: Option<(i32, usize)>,
+    },
+    /// A coefficient of the result of a checked operation doesn't fit in an i32, or a resultant doesn't fit in an i128
+    Overflow,
+}
+
+impl fmt::Display for NtruError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NtruError::NotInvertible => {
+                write!(f, "no multiplicative inverse exists in the given ring")
+            }
+            NtruError::ZeroDivisor => write!(f, "division by zero is not permitted"),
+            NtruError::InvalidModulus(m) => {
+                write!(f, "modulus must be a positive integer, but got {}", m)
+            }
+            NtruError::MessageTooLong { len, max } => write!(
+                f,
+                "message of {} bytes exceeds the maximum of {} bytes",
+                len, max
+            ),
+            NtruError::InvalidEncoding(reason) => write!(f, "invalid encoding: {}", reason),
+            NtruError::ParameterMismatch(reason) => write!(f, "parameter mismatch: {}", reason),
+            NtruError::InvalidParameters(reason) => write!(f, "invalid parameters: {}", reason),
+            NtruError::RingMismatch => write!(f, "elements of different rings can't be combined"),
+            NtruError::KeyGenerationFailed {
+                attempts,
+                shared_factor: Some((r, d)),
+            } => write!(
+                f,
+                "no invertible private key found in {} attempts; the last candidate shared a factor with Φ_{}(x) modulo {}",
+                attempts, d, r
+            ),
+            NtruError::KeyGenerationFailed { attempts, .. } => {
+                write!(f, "no invertible private key found in {} attempts", attempts)
+            }
+            NtruError::Overflow => write!(f, "integer arithmetic overflowed"),
+        }
+    }
+}
+
+impl std::error::Error for NtruError {}
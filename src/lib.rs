@@ -1,7 +1,27 @@
+pub mod coeff_stats;
+pub mod compact_poly;
+pub mod constant_time;
 pub mod convolution_polynomial;
+pub mod decryption_failure;
+pub mod error;
+pub mod multiplication;
+pub mod ntru_ciphertext;
 pub mod ntru_key;
 pub mod ntru_util;
+pub mod param_validation;
 pub mod params;
+pub mod poly_ring;
+pub mod ring_factorization;
+pub mod security_estimate;
+pub mod sparse_ternary;
 // Exported from ntru crate
+pub use coeff_stats::CoeffStats;
+pub use compact_poly::{PolyModP, PolyModQ};
 pub use convolution_polynomial::ConvPoly;
+pub use error::NtruError;
+pub use multiplication::MulBackend;
+pub use ntru_ciphertext::NtruCiphertext;
 pub use ntru_key::{NtruKeyPair, NtruPrivateKey, NtruPublicKey};
+pub use params::{NtruParams, ParamSet, ProductFormWeights};
+pub use poly_ring::{PolyRing, ReductionPoly, RingElement};
+pub use sparse_ternary::SparseTernaryPoly;
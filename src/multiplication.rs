@@ -0,0 +1,379 @@
+// Multiplication in Z[x]/(x^N - 1) is computed as an ordinary (linear) polynomial product followed by folding the
+// coefficient of x^i onto x^(i mod N). Every backend computes the linear product exactly over the integers, in i128 so
+// that no product of i32 coefficients can overflow, which makes their results identical to each other and to the
+// schoolbook product. The NTT backend works modulo primes instead, but with enough of them that the result is still
+// exact.
+
+/// Below this length the schoolbook product is faster than splitting the operands any further
+const KARATSUBA_THRESHOLD: usize = 64;
+/// From this length on Toom-Cook-4 is faster than Karatsuba
+const TOOM_COOK_THRESHOLD: usize = 256;
+/// From this length on the number-theoretic transform is faster than Toom-Cook-4
+const NTT_THRESHOLD: usize = 2048;
+
+/// The algorithm used to multiply polynomials
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MulBackend {
+    /// The O(N^2) product of every pair of coefficients
+    Schoolbook,
+    /// Karatsuba multiplication, which splits each operand in 2 and needs 3 half-size products, O(N^1.58)
+    Karatsuba,
+    /// Toom-Cook-4 multiplication, which splits each operand in 4 and needs 7 quarter-size products, O(N^1.40)
+    ToomCook4,
+    /// Number-theoretic transforms modulo several 30-bit primes, combined by the Chinese remainder theorem,
+    /// O(N log N). Falls back to Toom-Cook-4 for products too long or too large for the primes to represent.
+    Ntt,
+    /// Picks the fastest of the above for the size of the operands
+    #[default]
+    Auto,
+}
+
+impl MulBackend {
+    /// Returns the backend Auto resolves to for a shorter operand of the given length
+    fn resolve(self, len: usize) -> MulBackend {
+        match self {
+            MulBackend::Auto if len < KARATSUBA_THRESHOLD => MulBackend::Schoolbook,
+            MulBackend::Auto if len < TOOM_COOK_THRESHOLD => MulBackend::Karatsuba,
+            MulBackend::Auto if len < NTT_THRESHOLD => MulBackend::ToomCook4,
+            MulBackend::Auto => MulBackend::Ntt,
+            backend => backend,
+        }
+    }
+}
+
+/// Returns the product of two polynomials in Z\[x\]/(x^n - 1), given by their coefficients, using the given backend.
+/// The result has exactly n coefficients.
+pub(crate) fn cyclic_product(a: &[i32], b: &[i32], n: usize, backend: MulBackend) -> Vec<i128> {
+    let mut result = vec![0; n];
+    for (i, c) in polynomial_product(a, b, backend).into_iter().enumerate() {
+        result[i % n] += c;
+    }
+    result
+}
+
+/// Returns the product of two polynomials in Z\[x\], given by their coefficients, using the given backend. The result
+/// has a.len() + b.len() - 1 coefficients, or none if either polynomial has none.
+pub(crate) fn polynomial_product(a: &[i32], b: &[i32], backend: MulBackend) -> Vec<i128> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let a: Vec<i128> = a.iter().map(|&c| c as i128).collect();
+    let b: Vec<i128> = b.iter().map(|&c| c as i128).collect();
+    linear_product(&a, &b, backend)
+}
+
+/// Returns the linear product of two non-empty polynomials, which has a.len() + b.len() - 1 coefficients
+fn linear_product(a: &[i128], b: &[i128], backend: MulBackend) -> Vec<i128> {
+    let len = a.len().max(b.len());
+    // Splitting only pays off if both operands are long; multiplying by a short polynomial (as the Euclidean
+    // algorithm does with its quotients) is cheapest done directly
+    match backend.resolve(a.len().min(b.len())) {
+        MulBackend::Karatsuba if len >= 2 => karatsuba(a, b),
+        MulBackend::ToomCook4 if len >= 4 => toom_cook_4(a, b),
+        MulBackend::Ntt => {
+            ntt_product(a, b).unwrap_or_else(|| linear_product(a, b, MulBackend::ToomCook4))
+        }
+        _ => schoolbook(a, b),
+    }
+}
+
+fn schoolbook(a: &[i128], b: &[i128]) -> Vec<i128> {
+    let mut result = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        for (j, &y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+/// Splits both operands as a(x) = a0(x) + x^m*a1(x) and b(x) = b0(x) + x^m*b1(x), so that
+/// a(x)b(x) = a0b0 + x^m*((a0 + a1)(b0 + b1) - a0b0 - a1b1) + x^2m*a1b1
+fn karatsuba(a: &[i128], b: &[i128]) -> Vec<i128> {
+    let m = a.len().max(b.len()).div_ceil(2);
+    let (a0, a1) = split(a, m);
+    let (b0, b1) = split(b, m);
+
+    let low = product_or_empty(a0, b0);
+    let high = product_or_empty(a1, b1);
+    let mid = product_or_empty(&add(a0, a1), &add(b0, b1));
+
+    let mut result = vec![0; a.len() + b.len() - 1];
+    accumulate(&mut result, &low, 0, 1);
+    accumulate(&mut result, &mid, m, 1);
+    accumulate(&mut result, &low, m, -1);
+    accumulate(&mut result, &high, m, -1);
+    accumulate(&mut result, &high, 2 * m, 1);
+    result.truncate(a.len() + b.len() - 1);
+    result
+}
+
+/// The points the operands are evaluated at, besides infinity
+const TOOM_COOK_POINTS: [i128; 6] = [0, 1, -1, 2, -2, 3];
+
+/// Splits both operands into 4 parts of m coefficients, viewing them as polynomials A(y), B(y) of degree 3 in
+/// y = x^m. The product C(y) = A(y)B(y) has degree 6, so it's determined by its values at the 6 points above and its
+/// leading coefficient (its "value at infinity"), each of which is a product of m-coefficient polynomials. The
+/// coefficients of C(y) are recovered by Newton interpolation, whose divided differences are exact for polynomials
+/// with integer coefficients.
+fn toom_cook_4(a: &[i128], b: &[i128]) -> Vec<i128> {
+    let m = a.len().max(b.len()).div_ceil(4);
+    let a_parts = split_4(a, m);
+    let b_parts = split_4(b, m);
+
+    // Pointwise products C(t) = A(t)B(t), and the leading coefficient a3*b3
+    let values: Vec<Vec<i128>> = TOOM_COOK_POINTS
+        .iter()
+        .map(|&t| product_or_empty(&evaluate(&a_parts, t), &evaluate(&b_parts, t)))
+        .collect();
+    let c0 = values[0].clone();
+    let c6 = product_or_empty(&a_parts[3], &b_parts[3]);
+
+    // E(y) = (C(y) - c0 - c6*y^6) / y has degree 4 and is known at the 5 non-zero points
+    let nodes = &TOOM_COOK_POINTS[1..];
+    let mut divided: Vec<Vec<i128>> = nodes
+        .iter()
+        .zip(&values[1..])
+        .map(|(&t, value)| {
+            let mut e = value.clone();
+            accumulate(&mut e, &c0, 0, -1);
+            accumulate(&mut e, &c6, 0, -t.pow(6));
+            e.iter().map(|c| c / t).collect()
+        })
+        .collect();
+
+    // Newton divided differences
+    for j in 1..nodes.len() {
+        for i in (j..nodes.len()).rev() {
+            let denom = nodes[i] - nodes[i - j];
+            let prev = divided[i - 1].clone();
+            divided[i] = sub(&divided[i], &prev).iter().map(|c| c / denom).collect();
+        }
+    }
+
+    // Convert the Newton form to the coefficients e0, ..., e4 of E(y), by Horner's rule on
+    // E(y) = d0 + (y - x0)(d1 + (y - x1)(d2 + ...))
+    let mut coeffs: Vec<Vec<i128>> = vec![divided[nodes.len() - 1].clone()];
+    for i in (0..nodes.len() - 1).rev() {
+        // coeffs <-- coeffs*(y - x_i) + d_i
+        let mut next = vec![Vec::new(); coeffs.len() + 1];
+        for (k, coeff) in coeffs.iter().enumerate() {
+            next[k + 1] = add(&next[k + 1], coeff);
+            next[k] = sub(
+                &next[k],
+                &coeff.iter().map(|c| c * nodes[i]).collect::<Vec<_>>(),
+            );
+        }
+        next[0] = add(&next[0], &divided[i]);
+        coeffs = next;
+    }
+
+    // C(y) = c0 + y*E(y) + c6*y^6, with y = x^m
+    let mut result = vec![0; a.len() + b.len() - 1];
+    accumulate(&mut result, &c0, 0, 1);
+    for (k, coeff) in coeffs.iter().enumerate() {
+        accumulate(&mut result, coeff, (k + 1) * m, 1);
+    }
+    accumulate(&mut result, &c6, 6 * m, 1);
+    result.truncate(a.len() + b.len() - 1);
+    result
+}
+
+// The linear product of a(x) and b(x) is computed modulo each of the primes below by a transform of the next power
+// of two at least as long as the product, which is a cyclic convolution with enough zero padding that nothing wraps
+// around. As long as every coefficient of the product lies strictly between -M/2 and M/2, where M is the product of
+// the primes (about 2^86), the residues determine it exactly.
+
+/// Primes of the form c*2^k + 1 below 2^30, each with 3 as a primitive root
+const NTT_PRIMES: [u64; 3] = [998_244_353, 469_762_049, 167_772_161];
+/// The longest transform every prime supports, since 2^23 divides each p - 1
+const NTT_MAX_LEN: usize = 1 << 23;
+
+/// Returns the linear product of two non-empty polynomials computed with number-theoretic transforms, or None if
+/// it's too long for the transform or its coefficients could be too large to reconstruct exactly
+fn ntt_product(a: &[i128], b: &[i128]) -> Option<Vec<i128>> {
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    if size > NTT_MAX_LEN {
+        return None;
+    }
+
+    // Every coefficient of the product is at most max|a_i| * max|b_j| * min(len(a), len(b)) in absolute value
+    let max_abs = |p: &[i128]| p.iter().map(|c| c.unsigned_abs()).max().unwrap_or(0);
+    let modulus: u128 = NTT_PRIMES.iter().map(|&p| p as u128).product();
+    let bound = max_abs(a)
+        .checked_mul(max_abs(b))?
+        .checked_mul(a.len().min(b.len()) as u128)?;
+    if bound >= modulus / 2 {
+        return None;
+    }
+
+    let residues: Vec<Vec<u64>> = NTT_PRIMES
+        .iter()
+        .map(|&p| {
+            let reduce = |poly: &[i128]| {
+                // Coefficients that fit in an i64 avoid the much slower i128 division
+                let mut values: Vec<u64> = poly
+                    .iter()
+                    .map(|&c| match i64::try_from(c) {
+                        Ok(c) => c.rem_euclid(p as i64) as u64,
+                        Err(_) => c.rem_euclid(p as i128) as u64,
+                    })
+                    .collect();
+                values.resize(size, 0);
+                values
+            };
+            let (mut fa, mut fb) = (reduce(a), reduce(b));
+            ntt(&mut fa, p, false);
+            ntt(&mut fb, p, false);
+            for (x, y) in fa.iter_mut().zip(&fb) {
+                *x = *x * y % p;
+            }
+            ntt(&mut fa, p, true);
+            fa
+        })
+        .collect();
+
+    // Garner's algorithm: the product's coefficient is x = r0 + p0*t1 + p0*p1*t2, with each ti reduced modulo pi,
+    // taken from (-M/2, M/2)
+    let [p0, p1, p2] = NTT_PRIMES;
+    let p01 = p0 * p1;
+    let p0_inv = pow_mod(p0, p1 - 2, p1);
+    let p01_inv = pow_mod(p01, p2 - 2, p2);
+    Some(
+        (0..len)
+            .map(|i| {
+                let [r0, r1, r2] = [residues[0][i], residues[1][i], residues[2][i]];
+                let t1 = (r1 + p1 - r0 % p1) % p1 * p0_inv % p1;
+                let x1 = r0 + p0 * t1;
+                let t2 = (r2 + p2 - x1 % p2) % p2 * p01_inv % p2;
+                let x = x1 as u128 + p01 as u128 * t2 as u128;
+                if x > modulus / 2 {
+                    x as i128 - modulus as i128
+                } else {
+                    x as i128
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Transforms the values in place into their evaluations at the powers of a primitive len-th root of unity modulo
+/// p, or back again if `invert` is set. The length must be a power of two.
+fn ntt(values: &mut [u64], p: u64, invert: bool) {
+    let n = values.len();
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let mut root = pow_mod(3, (p - 1) / len as u64, p);
+        if invert {
+            root = pow_mod(root, p - 2, p);
+        }
+        let mut twiddles = Vec::with_capacity(len / 2);
+        let mut w = 1;
+        for _ in 0..len / 2 {
+            twiddles.push(w);
+            w = w * root % p;
+        }
+        for chunk in values.chunks_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+            for ((x, y), &w) in low.iter_mut().zip(high).zip(&twiddles) {
+                let (u, v) = (*x, *y * w % p);
+                *x = if u + v >= p { u + v - p } else { u + v };
+                *y = if u >= v { u - v } else { u + p - v };
+            }
+        }
+        len <<= 1;
+    }
+
+    if invert {
+        let n_inv = pow_mod(n as u64, p - 2, p);
+        for x in values.iter_mut() {
+            *x = *x * n_inv % p;
+        }
+    }
+}
+
+/// Returns base^exp mod m, for m below 2^32
+pub(crate) fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Splits a polynomial into its first m coefficients and the rest, either of which may be empty
+fn split(a: &[i128], m: usize) -> (&[i128], &[i128]) {
+    a.split_at(m.min(a.len()))
+}
+
+/// Splits a polynomial into 4 parts of at most m coefficients
+fn split_4(a: &[i128], m: usize) -> [Vec<i128>; 4] {
+    let mut parts: [Vec<i128>; 4] = Default::default();
+    for (part, chunk) in parts.iter_mut().zip(a.chunks(m)) {
+        *part = chunk.to_vec();
+    }
+    parts
+}
+
+/// Evaluates a0 + a1*t + a2*t^2 + a3*t^3, where the ai are polynomials
+fn evaluate(parts: &[Vec<i128>; 4], t: i128) -> Vec<i128> {
+    parts.iter().rev().fold(Vec::new(), |acc, part| {
+        add(&acc.iter().map(|c| c * t).collect::<Vec<_>>(), part)
+    })
+}
+
+/// Returns the linear product of two polynomials, or the empty polynomial if either is empty
+fn product_or_empty(a: &[i128], b: &[i128]) -> Vec<i128> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    linear_product(a, b, MulBackend::Auto)
+}
+
+fn add(a: &[i128], b: &[i128]) -> Vec<i128> {
+    let mut result = a.to_vec();
+    accumulate(&mut result, b, 0, 1);
+    result
+}
+
+fn sub(a: &[i128], b: &[i128]) -> Vec<i128> {
+    let mut result = a.to_vec();
+    accumulate(&mut result, b, 0, -1);
+    result
+}
+
+/// Adds scale * x^offset * b(x) to the result, growing it as needed. Since the operands are split into equal parts,
+/// the partial products can reach past the end of the final product; those coefficients cancel out to zero and are
+/// truncated once the product is complete.
+fn accumulate(result: &mut Vec<i128>, b: &[i128], offset: usize, scale: i128) {
+    if result.len() < offset + b.len() {
+        result.resize(offset + b.len(), 0);
+    }
+    for (i, &c) in b.iter().enumerate() {
+        result[offset + i] += scale * c;
+    }
+}
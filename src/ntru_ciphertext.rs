@@ -0,0 +1,98 @@
+use crate::compact_poly::PolyModQ;
+use crate::error::NtruError;
+
+// A message longer than fits within a single polynomial is split into blocks that are encrypted separately, each with
+// its own random perturbation r(x). Before encryption, every block's plaintext is prefixed with its index and the
+// total number of blocks (both big-endian u16s), so that decryption can detect blocks that were reordered or dropped
+// even though the container's own header isn't authenticated. Nothing ties a block to the message it came from, though,
+// so a block swapped in from another ciphertext with the same number of blocks goes unnoticed.
+//
+// The container is encoded as the block count and total message length (both big-endian u32s), followed by every
+// block as its number of coefficients (a big-endian u32) and the coefficients themselves (big-endian u16s, since they're
+// reduced modulo q).
+
+/// The number of bytes of the index and block count prefixed to every block's plaintext
+pub(crate) const BLOCK_HEADER_BYTES: usize = 4;
+
+/// A ciphertext consisting of one or more encrypted polynomial blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct NtruCiphertext {
+    /// The encrypted blocks, in order
+    blocks: Vec<PolyModQ>,
+    /// The length of the encrypted message in bytes
+    total_len: usize,
+}
+
+impl NtruCiphertext {
+    /// Constructs a ciphertext from its encrypted blocks and the length of the message they encrypt
+    pub fn new(blocks: Vec<PolyModQ>, total_len: usize) -> NtruCiphertext {
+        NtruCiphertext { blocks, total_len }
+    }
+
+    /// Returns the encrypted blocks, in order
+    pub fn blocks(&self) -> &[PolyModQ] {
+        &self.blocks
+    }
+
+    /// Returns the number of encrypted blocks
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the length of the encrypted message in bytes
+    pub fn total_len(&self) -> usize {
+        self.total_len
+    }
+
+    /// Serializes the ciphertext into a byte vector
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(self.blocks.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&(self.total_len as u32).to_be_bytes());
+        for block in &self.blocks {
+            buf.extend_from_slice(&(block.n() as u32).to_be_bytes());
+            buf.extend_from_slice(&block.to_be_bytes());
+        }
+        buf
+    }
+
+    /// Deserializes a byte vector into a ciphertext. Returns an error if the bytes are truncated, have trailing data,
+    /// record no blocks at all or don't contain the number of blocks the header records.
+    pub fn from_be_bytes(buf: &[u8]) -> Result<NtruCiphertext, NtruError> {
+        let mut rest = buf;
+        let block_count = read_u32(&mut rest)? as usize;
+        let total_len = read_u32(&mut rest)? as usize;
+        // Even an empty message is encrypted as a single block
+        if block_count == 0 {
+            return Err(NtruError::InvalidEncoding("ciphertext contains no blocks"));
+        }
+
+        let mut blocks = Vec::new();
+        while !rest.is_empty() {
+            let num_coeffs = read_u32(&mut rest)? as usize;
+            let num_bytes = num_coeffs
+                .checked_mul(size_of::<u16>())
+                .filter(|&num_bytes| num_bytes <= rest.len())
+                .ok_or(NtruError::InvalidEncoding("ciphertext block is truncated"))?;
+            let (block, tail) = rest.split_at(num_bytes);
+            blocks.push(PolyModQ::from_be_bytes(block)?);
+            rest = tail;
+        }
+        if blocks.len() != block_count {
+            return Err(NtruError::InvalidEncoding(
+                "ciphertext doesn't contain the number of blocks its header records",
+            ));
+        }
+
+        Ok(NtruCiphertext { blocks, total_len })
+    }
+}
+
+/// Reads a big-endian u32 from the front of the buffer and advances past it
+fn read_u32(buf: &mut &[u8]) -> Result<u32, NtruError> {
+    let (bytes, rest) = buf
+        .split_first_chunk::<4>()
+        .ok_or(NtruError::InvalidEncoding("ciphertext header is truncated"))?;
+    *buf = rest;
+    Ok(u32::from_be_bytes(*bytes))
+}
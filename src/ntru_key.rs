@@ -1,6 +1,15 @@
-use crate::convolution_polynomial::{ternary_polynomial, ConvPoly};
-use crate::ntru_util::{deserialize, serialize};
-use crate::params::*;
+use crate::compact_poly::{PolyModP, PolyModQ};
+use crate::convolution_polynomial::ConvPoly;
+use crate::error::NtruError;
+use crate::ntru_ciphertext::{NtruCiphertext, BLOCK_HEADER_BYTES};
+use crate::ntru_util::{deserialize, max_message_len, serialize};
+use crate::params::{NtruParams, ProductFormWeights};
+use crate::poly_ring::{PolyRing, RingElement};
+use crate::sparse_ternary::SparseTernaryPoly;
+
+/// The number of candidates for the private key tried before key generation gives up. A random candidate is invertible
+/// with high probability for any usable parameter set, so running out means the ring all but rules them out.
+const MAX_KEY_ATTEMPTS: usize = 100;
 
 #[derive(Clone)]
 /// An NTRU key pair
@@ -12,116 +21,382 @@ pub struct NtruKeyPair {
 }
 
 impl NtruKeyPair {
-    /// Generates a new public/private NTRU key pair
-    pub fn new() -> NtruKeyPair {
-        let k_priv = NtruPrivateKey::new();
+    /// Generates a new public/private NTRU key pair over the given parameter set. Returns an error if the parameter
+    /// set can't be used to generate keys.
+    pub fn new(params: &NtruParams) -> Result<NtruKeyPair, NtruError> {
+        let k_priv = NtruPrivateKey::new(params)?;
         let k_pub = NtruPublicKey::new(&k_priv);
-        NtruKeyPair {
+        Ok(NtruKeyPair {
             public: k_pub,
             private: k_priv,
-        }
+        })
+    }
+
+    /// Generates a new key pair whose private key is in product form, f(x) = 1 + p*(f1(x)*f2(x) + f3(x)), with the
+    /// factors weighted by `weights`. Returns an error if the parameter set or weights can't be used to generate keys.
+    pub fn new_product_form(
+        params: &NtruParams,
+        weights: &ProductFormWeights,
+    ) -> Result<NtruKeyPair, NtruError> {
+        let k_priv = NtruPrivateKey::new_product_form(params, weights)?;
+        let k_pub = NtruPublicKey::new(&k_priv);
+        Ok(NtruKeyPair {
+            public: k_pub,
+            private: k_priv,
+        })
     }
 }
 
 #[derive(Clone)]
 /// A public key used in the NTRU encryption scheme
 pub struct NtruPublicKey {
-    h: ConvPoly,
+    /// The parameter set this key was generated with
+    params: NtruParams,
+    /// h(x) ≡ Fq(x)*g(x) within the ring (Z/QZ)\[x\]/(x^N - 1)
+    h: PolyModQ,
 }
 
 impl NtruPublicKey {
     /// Generates a public key given a corresponding private key
     fn new(k_priv: &NtruPrivateKey) -> NtruPublicKey {
-        // Generate f inverse over Q
-        let f_inv = &k_priv.f_q;
-        // Public key generated as f inverse Q * g
-        let h = f_inv.mul(&k_priv.g, N);
-        NtruPublicKey { h }
+        NtruPublicKey {
+            params: k_priv.params,
+            h: k_priv.h.clone(),
+        }
+    }
+
+    /// Returns the parameter set this key was generated with
+    pub fn params(&self) -> &NtruParams {
+        &self.params
     }
 
     /// Encrypts a convolution polynomial represented message using the NTRU encryption scheme.
     /// Used for successive layers of encryption after a message has already been serialized.
-    pub fn encrypt_poly(&self, msg: ConvPoly) -> ConvPoly {
-        // Compute r(x) as a random perturbation in T(d, d)
-        let rand = ternary_polynomial(N, D, D);
-        // Compute the encrypted message e(x) ≡ m(x) + p*r(x)*h(x)  (mod q)
-        let p = ConvPoly::constant(P);
-        let enc_msg = msg.add(&p.mul(&rand.mul(&self.h, N), N)).modulo(Q);
-        enc_msg
+    /// Returns an error if the message has more than N coefficients.
+    pub fn encrypt_poly(&self, msg: ConvPoly) -> Result<ConvPoly, NtruError> {
+        if msg.coeffs.len() > self.params.n {
+            return Err(NtruError::ParameterMismatch(
+                "message polynomial has more than N coefficients",
+            ));
+        }
+        Ok(self.encrypt_element(&self.expand_h()?, &msg)?.to_poly())
     }
 
-    /// Encrypts an ASCII byte vector of a message using the NTRU encryption scheme
+    /// Encrypts a byte vector of a message using the NTRU encryption scheme. Every byte value is supported, and
+    /// messages longer than fit within a single polynomial are split into blocks that are encrypted separately.
     /// Should be used as a first layer of encryption since it serializes the message.
-    pub fn encrypt_bytes(&self, msg: Vec<u8>) -> ConvPoly {
-        self.encrypt_poly(serialize(msg))
+    /// Returns an error if the message needs more than 65535 blocks.
+    pub fn encrypt_bytes(&self, msg: Vec<u8>) -> Result<NtruCiphertext, NtruError> {
+        let capacity = block_capacity(&self.params)?;
+        // An empty message is still encrypted as a single (empty) block
+        let chunks: Vec<&[u8]> = if msg.is_empty() {
+            vec![&[]]
+        } else {
+            msg.chunks(capacity).collect()
+        };
+        if chunks.len() > u16::MAX as usize {
+            return Err(NtruError::MessageTooLong {
+                len: msg.len(),
+                max: capacity * u16::MAX as usize,
+            });
+        }
+
+        let h = self.expand_h()?;
+        let blocks = chunks
+            .iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let mut block = Vec::with_capacity(BLOCK_HEADER_BYTES + chunk.len());
+                block.extend_from_slice(&(index as u16).to_be_bytes());
+                block.extend_from_slice(&(chunks.len() as u16).to_be_bytes());
+                block.extend_from_slice(chunk);
+                PolyModQ::from_element(&self.encrypt_element(&h, &serialize(block, &self.params)?)?)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(NtruCiphertext::new(blocks, msg.len()))
     }
 
-    /// Serializes the public key into a byte vector
+    /// Serializes the public key into a byte vector of 2 bytes per coefficient
     pub fn to_be_bytes(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(self.h.coeffs.len() * size_of::<i32>());
-        for coeff in &self.h.coeffs {
-            buf.extend_from_slice(&coeff.to_be_bytes());
-        }
-        buf
+        self.h.to_be_bytes()
     }
 
-    /// Deserializes a byte vector into an NTRU public key
-    pub fn from_be_bytes(buf: &[u8]) -> NtruPublicKey {
-        let mut coeffs = Vec::with_capacity(buf.len() / size_of::<i32>());
-        for chunk in buf.chunks(size_of::<i32>()) {
-            let mut bytes = [0; size_of::<i32>()];
-            bytes.copy_from_slice(chunk);
-            coeffs.push(i32::from_be_bytes(bytes));
-        }
-        NtruPublicKey {
-            h: ConvPoly { coeffs },
-        }
+    /// Deserializes a byte vector into an NTRU public key belonging to the given parameter set. Returns an error if
+    /// the bytes don't encode a polynomial of exactly N coefficients reduced modulo q.
+    pub fn from_be_bytes(buf: &[u8], params: &NtruParams) -> Result<NtruPublicKey, NtruError> {
+        params.ensure_usable()?;
+        let h = PolyModQ::from_be_bytes(buf)?;
+        h.to_element(&PolyRing::new(params.n, params.q)?)?;
+        Ok(NtruPublicKey { params: *params, h })
+    }
+
+    /// Expands h(x) into an element of (Z/QZ)\[x\]/(x^N - 1)
+    fn expand_h(&self) -> Result<RingElement, NtruError> {
+        self.h
+            .to_element(&PolyRing::new(self.params.n, self.params.q)?)
     }
+
+    /// Encrypts a message polynomial of at most N coefficients with the expanded public key h(x)
+    fn encrypt_element(&self, h: &RingElement, msg: &ConvPoly) -> Result<RingElement, NtruError> {
+        let NtruParams { n, p, d, .. } = self.params;
+        let m = h.ring().element(msg);
+        // Compute r(x) as a random perturbation in T(d, d)
+        let r = SparseTernaryPoly::random(n, d, d)?;
+        // Compute the encrypted message e(x) ≡ m(x) + p*r(x)*h(x)  (mod q)
+        Ok(m + p * (&r * h))
+    }
+}
+
+// A product-form f(x) = 1 + p*F(x) is congruent to 1 modulo p, so decryption needs no inverse Fp(x): the final
+// multiplication by it is a reduction modulo p. Writing F(x) = f1(x)*f2(x) + f3(x) with very sparse factors also
+// makes e(x)*f(x) = e(x) + p*(f1(x)*(f2(x)*e(x)) + f3(x)*e(x)) three sparse convolutions.
+
+#[derive(Clone)]
+/// The form the private polynomial f(x) is stored in
+enum PrivateKeyForm {
+    /// f(x) drawn from T(D+1, D), along with its inverse modulo P within the ring (Z/PZ)\[x\]/(x^N - 1)
+    Dense { f: SparseTernaryPoly, f_p: PolyModP },
+    /// f(x) = 1 + P*(f1(x)*f2(x) + f3(x)), with each fi(x) drawn from T(di, di)
+    Product {
+        f1: SparseTernaryPoly,
+        f2: SparseTernaryPoly,
+        f3: SparseTernaryPoly,
+    },
 }
 
 #[derive(Clone)]
 /// A private key used in the NTRU encryption scheme
 pub struct NtruPrivateKey {
-    /// A random polynomial generated over T(D+1, D)
-    f: ConvPoly,
-    /// The inverse of f(x) modulo P within the ring (Z/PZ)\[x\]/(x^N - 1)
-    f_p: ConvPoly,
-    /// The inverse of f(x) modulo Q within the ring (Z/QZ)\[x\]/(x^N - 1)
-    f_q: ConvPoly,
-    /// A random polynomial generated over T(D, D)
-    g: ConvPoly,
+    /// The parameter set this key was generated with
+    params: NtruParams,
+    /// The private polynomial f(x), either dense or in product form
+    form: PrivateKeyForm,
+    /// The public key h(x) ≡ Fq(x)*g(x), from which the key pair's public half is derived. Neither Fq(x) nor g(x) is
+    /// needed after key generation, so they aren't kept.
+    h: PolyModQ,
 }
 
 impl NtruPrivateKey {
-    /// Generates a new random NTRU private key
-    fn new() -> NtruPrivateKey {
-        loop {
-            let f = ternary_polynomial(N, D + 1, D);
-            let f_p = f.inverse(P, N);
-            let f_q = f.inverse(Q, N);
-            match (f_p, f_q) {
-                (Ok(f_p), Ok(f_q)) => {
-                    let g = ternary_polynomial(N, D, D);
-                    return NtruPrivateKey { f, f_p, f_q, g };
+    /// Generates a new random NTRU private key over the given parameter set
+    fn new(params: &NtruParams) -> Result<NtruPrivateKey, NtruError> {
+        params.ensure_usable()?;
+        let NtruParams { n, p, q, d } = *params;
+        let (ring_p, ring_q) = (PolyRing::new(n, p)?, PolyRing::new(n, q)?);
+        let mut rejected = None;
+        for _ in 0..MAX_KEY_ATTEMPTS {
+            let f = SparseTernaryPoly::random(n, d + 1, d)?;
+            // Try again with a new f(x) if it isn't invertible modulo p or q. The resultant would show that more
+            // cheaply, but its remainder sequence takes a time that depends on f(x), where a failed constant-time
+            // inversion doesn't.
+            let f_p = match f.to_element_constant_time(&ring_p)?.inverse() {
+                Err(NtruError::NotInvertible) => {
+                    rejected = Some((f.to_poly(), p));
+                    continue;
                 }
-                _ => continue,
-            }
+                f_p => PolyModP::from_element(&f_p?)?,
+            };
+            let f_q = match f.to_element_constant_time(&ring_q)?.inverse() {
+                Err(NtruError::NotInvertible) => {
+                    rejected = Some((f.to_poly(), q));
+                    continue;
+                }
+                f_q => f_q?,
+            };
+            return Ok(NtruPrivateKey {
+                params: *params,
+                form: PrivateKeyForm::Dense { f, f_p },
+                h: public_poly(&f_q, d)?,
+            });
+        }
+        Err(key_generation_failed(rejected, n))
+    }
+
+    /// Generates a new random NTRU private key over the given parameter set, with f(x) in product form
+    fn new_product_form(
+        params: &NtruParams,
+        weights: &ProductFormWeights,
+    ) -> Result<NtruPrivateKey, NtruError> {
+        params.ensure_usable()?;
+        weights.ensure_usable(params)?;
+        let NtruParams { n, p, q, d } = *params;
+        let ProductFormWeights { d1, d2, d3 } = *weights;
+        let ring_q = PolyRing::new(n, q)?;
+        let mut rejected = None;
+        for _ in 0..MAX_KEY_ATTEMPTS {
+            let f1 = SparseTernaryPoly::random(n, d1, d1)?;
+            let f2 = SparseTernaryPoly::random(n, d2, d2)?;
+            let f3 = SparseTernaryPoly::random(n, d3, d3)?;
+            // f(x) = 1 + p*(f1(x)*f2(x) + f3(x)) (mod q)
+            let f = product_form_mul(&ring_q, p, [&f1, &f2, &f3], &ring_q.constant(1))?;
+            // Try again with new factors if f(x) isn't invertible. f(x) ≡ 1 (mod p), so it always is modulo p.
+            let f_q = match f.inverse() {
+                Err(NtruError::NotInvertible) => {
+                    rejected = Some((f.to_poly(), q));
+                    continue;
+                }
+                f_q => f_q?,
+            };
+            return Ok(NtruPrivateKey {
+                params: *params,
+                form: PrivateKeyForm::Product { f1, f2, f3 },
+                h: public_poly(&f_q, d)?,
+            });
         }
+        Err(key_generation_failed(rejected, n))
+    }
+
+    /// Returns the parameter set this key was generated with
+    pub fn params(&self) -> &NtruParams {
+        &self.params
     }
 
-    /// Decrypts a polynomial-encoded message using the NTRU encryption scheme into a byte vector
+    /// Returns whether f(x) is stored in product form, f(x) = 1 + p*(f1(x)*f2(x) + f3(x))
+    pub fn is_product_form(&self) -> bool {
+        matches!(self.form, PrivateKeyForm::Product { .. })
+    }
+
+    /// Decrypts a ciphertext using the NTRU encryption scheme into a byte vector
     /// ONLY FUNCTIONAL ON SINGLE LAYER ENCRYPTION ; MULTIPLE LAYERS WILL BREAK!
-    pub fn decrypt_to_bytes(&self, enc_msg: ConvPoly) -> Vec<u8> {
-        deserialize(self.decrypt_to_poly(enc_msg))
+    /// Returns an error if the ciphertext doesn't belong to this key's parameter set, doesn't decrypt to a valid
+    /// message, or has blocks that were reordered or dropped.
+    pub fn decrypt_to_bytes(&self, enc_msg: &NtruCiphertext) -> Result<Vec<u8>, NtruError> {
+        let block_count = enc_msg.block_count();
+        // Even an empty message is encrypted as a single block, so a ciphertext without any has had them stripped
+        if block_count == 0 {
+            return Err(NtruError::InvalidEncoding("ciphertext contains no blocks"));
+        }
+        let mut msg = Vec::with_capacity(
+            enc_msg
+                .total_len()
+                .min(block_count.saturating_mul(max_message_len(&self.params))),
+        );
+        let ring_q = PolyRing::new(self.params.n, self.params.q)?;
+        for (index, block) in enc_msg.blocks().iter().enumerate() {
+            let e = block.to_element(&ring_q)?;
+            let m = ConvPoly {
+                coeffs: self.decrypt_element(&e)?.center_lift_constant_time(),
+            };
+            let plain = deserialize(m, &self.params)?;
+            let (header, chunk) =
+                plain
+                    .split_at_checked(BLOCK_HEADER_BYTES)
+                    .ok_or(NtruError::InvalidEncoding(
+                        "ciphertext block is missing its header",
+                    ))?;
+            if usize::from(u16::from_be_bytes([header[0], header[1]])) != index {
+                return Err(NtruError::InvalidEncoding(
+                    "ciphertext blocks are out of order",
+                ));
+            }
+            if usize::from(u16::from_be_bytes([header[2], header[3]])) != block_count {
+                return Err(NtruError::InvalidEncoding(
+                    "ciphertext doesn't contain the number of blocks it was encrypted with",
+                ));
+            }
+            msg.extend_from_slice(chunk);
+        }
+        if msg.len() != enc_msg.total_len() {
+            return Err(NtruError::InvalidEncoding(
+                "decrypted message doesn't have the length the ciphertext records",
+            ));
+        }
+        Ok(msg)
     }
 
     /// Decrypts a polynomial-encoded message using the NTRU encryption scheme into another polynomial
     /// ONLY FUNCTIONAL ON MULTI-LAYERED ENCRYPTION : FINAL LAYER WILL BREAK!
-    pub fn decrypt_to_poly(&self, enc_msg: ConvPoly) -> ConvPoly {
-        // a(x) ≡ e(x) * f(x) (mod q)
-        let a = enc_msg.mul(&self.f, N).center_lift(Q);
-        // m(x) ≡ a(x) * Fp(x) (mod p)
-        let msg_poly = a.mul(&self.f_p, N).modulo(P);
-        msg_poly
+    /// Returns the message's N coefficients center-lifted into (-p/2, p/2], including zeros at the top, which
+    /// trimming would take a time to remove that depends on the message. Returns an error if the ciphertext has more
+    /// than N coefficients.
+    pub fn decrypt_to_poly(&self, enc_msg: ConvPoly) -> Result<ConvPoly, NtruError> {
+        if enc_msg.coeffs.len() > self.params.n {
+            return Err(NtruError::ParameterMismatch(
+                "ciphertext has more than N coefficients",
+            ));
+        }
+        let e = PolyRing::new(self.params.n, self.params.q)?.element(&enc_msg);
+        Ok(ConvPoly {
+            coeffs: self.decrypt_element(&e)?.center_lift_constant_time(),
+        })
+    }
+
+    /// Decrypts a ciphertext e(x) in (Z/QZ)\[x\]/(x^N - 1) into the message m(x) in (Z/PZ)\[x\]/(x^N - 1). Every step
+    /// works on all N coefficients without branching on them, so the time this takes doesn't depend on the key or the
+    /// message.
+    fn decrypt_element(&self, e: &RingElement) -> Result<RingElement, NtruError> {
+        let ring_p = PolyRing::new(self.params.n, self.params.p)?;
+        match &self.form {
+            PrivateKeyForm::Dense { f, f_p } => {
+                // a(x) ≡ e(x) * f(x) (mod q), center-lifted into Z[x]/(x^N - 1)
+                let a = f
+                    .to_element_constant_time(e.ring())?
+                    .mul_constant_time(e)?
+                    .center_lift_constant_time();
+                // m(x) ≡ a(x) * Fp(x) (mod p)
+                ring_p
+                    .element_constant_time(&a)?
+                    .mul_constant_time(&ring_p.element_constant_time(f_p.coeffs())?)
+            }
+            PrivateKeyForm::Product { f1, f2, f3 } => {
+                // a(x) ≡ e(x) + p*(f1(x)*(f2(x)*e(x)) + f3(x)*e(x)) (mod q), center-lifted into Z[x]/(x^N - 1)
+                let a = product_form_mul(e.ring(), self.params.p, [f1, f2, f3], e)?
+                    .center_lift_constant_time();
+                // m(x) ≡ a(x) (mod p), since f(x) ≡ 1 (mod p)
+                ring_p.element_constant_time(&a)
+            }
+        }
+    }
+}
+
+/// Returns b(x) + p*(f1(x)*(f2(x)*b(x)) + f3(x)*b(x)), which is f(x)*b(x) for the product-form key
+/// f(x) = 1 + p*(f1(x)*f2(x) + f3(x)), computed in constant time
+fn product_form_mul(
+    ring: &PolyRing,
+    p: i32,
+    [f1, f2, f3]: [&SparseTernaryPoly; 3],
+    b: &RingElement,
+) -> Result<RingElement, NtruError> {
+    let f2_b = f2.to_element_constant_time(ring)?.mul_constant_time(b)?;
+    let f1_f2_b = f1
+        .to_element_constant_time(ring)?
+        .mul_constant_time(&f2_b)?;
+    let f3_b = f3.to_element_constant_time(ring)?.mul_constant_time(b)?;
+    let coeffs: Vec<i64> = (0..ring.n())
+        .map(|i| {
+            let sum = f1_f2_b.coeffs()[i] as i64 + f3_b.coeffs()[i] as i64;
+            b.coeffs()[i] as i64 + p as i64 * sum
+        })
+        .collect();
+    ring.element_constant_time(&coeffs)
+}
+
+/// Returns the public key h(x) ≡ Fq(x)*g(x) (mod q) for a fresh g(x) drawn from T(d, d)
+fn public_poly(f_q: &RingElement, d: usize) -> Result<PolyModQ, NtruError> {
+    let g = SparseTernaryPoly::random(f_q.ring().n(), d, d)?;
+    PolyModQ::from_element(
+        &g.to_element_constant_time(f_q.ring())?
+            .mul_constant_time(f_q)?,
+    )
+}
+
+/// Returns the error for a key generation that rejected every candidate, explaining the last rejected f(x) with the
+/// factor of x^N - 1 it shared modulo the modulus it failed to invert modulo. Its resultants take a time that depends
+/// on f(x), which is harmless once f(x) has been discarded.
+fn key_generation_failed(rejected: Option<(ConvPoly, i32)>, n: usize) -> NtruError {
+    NtruError::KeyGenerationFailed {
+        attempts: MAX_KEY_ATTEMPTS,
+        shared_factor: rejected.and_then(|(f, m)| f.shared_factor(m, n)),
+    }
+}
+
+/// Returns the number of message bytes that fit within a single ciphertext block of the given parameter set. Returns
+/// an error if the ring is too small to hold a block header.
+fn block_capacity(params: &NtruParams) -> Result<usize, NtruError> {
+    match max_message_len(params).checked_sub(BLOCK_HEADER_BYTES) {
+        Some(capacity) if capacity > 0 => Ok(capacity),
+        _ => Err(NtruError::InvalidParameters(
+            "N is too small to hold a ciphertext block",
+        )),
     }
 }
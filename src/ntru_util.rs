@@ -1,97 +1,121 @@
+use crate::constant_time::{self, negative_mask};
 use crate::convolution_polynomial::*;
-use crate::params::*;
+use crate::error::NtruError;
+use crate::params::NtruParams;
 
-/// Takes in a plain message encoded in ASCII and returns a convolution polynomial with coefficients representing that message
-pub fn serialize(plain_msg: Vec<u8>) -> ConvPoly {
-    assert!(
-        plain_msg.len() * 5 <= N,
-        "serialize: Message cannot exceed N - 1 in length"
-    );
-    // Convert the message to a vector of ternary digits
-    let mut digit_vec = Vec::with_capacity(plain_msg.len() * 5);
-    for c in plain_msg {
-        let arr = ternary(c.into());
-        digit_vec.extend_from_slice(&arr);
-    }
+// Messages are encoded with the NTRUEncrypt 3-bits-to-2-trits mapping. The message is prefixed with its length as a
+// big-endian u16 and the resulting bit string is split into groups of 3 bits (the last group padded with zeros). Each
+// group v in [0, 8) becomes the pair of trits (v / 3, v % 3), where a trit of 2 is stored as the coefficient -1. The
+// pair (-1, -1) would represent 8, so it never occurs in a valid encoding. Every byte value, including 0x00, maps to
+// trits and back, and the explicit length means trailing zero bytes aren't lost when trailing zero coefficients are.
+
+/// The number of bytes of the length prefix
+const LENGTH_BYTES: usize = 2;
 
-    ConvPoly { coeffs: digit_vec }
+/// Returns the maximum number of message bytes that fit within a single polynomial of the given parameter set
+pub fn max_message_len(params: &NtruParams) -> usize {
+    let bits = params.n / 2 * 3;
+    (bits.saturating_sub(8 * LENGTH_BYTES) / 8).min(u16::MAX as usize)
 }
 
-/// Converts a 32 bit integer to a balanced ternary representation in the form of a 5-integer array
-/// Max value is 242
-fn ternary(mut c: i32) -> [i32; 5] {
-    assert!(c < 242 && c >= 0);
-    if c == 0 {
-        return [0; 5];
+/// Takes in a plain message of arbitrary bytes and returns a convolution polynomial with ternary coefficients
+/// representing that message. Returns an error if the message doesn't fit within a single polynomial of the given
+/// parameter set, or if p is too small to hold ternary coefficients.
+pub fn serialize(plain_msg: Vec<u8>, params: &NtruParams) -> Result<ConvPoly, NtruError> {
+    check_ternary_modulus(params)?;
+    let max = max_message_len(params);
+    if plain_msg.len() > max {
+        return Err(NtruError::MessageTooLong {
+            len: plain_msg.len(),
+            max,
+        });
     }
 
-    let mut digits = [0; 5];
-    for i in (0..5).rev() {
-        if c == 0 {
-            break;
-        }
-        let rem_temp = c % 3;
-        c /= 3;
-        digits[i] = if rem_temp == 2 { -1 } else { rem_temp };
-    }
-    digits
-}
+    let mut bytes = Vec::with_capacity(LENGTH_BYTES + plain_msg.len());
+    bytes.extend_from_slice(&(plain_msg.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(&plain_msg);
 
-/// Deserializes a convolution polynomial into the message it represents as a vector
-/// of u8s
-pub fn deserialize(ser_msg: ConvPoly) -> Vec<u8> {
-    let coeffs = ser_msg.coeffs;
-    let mut ret: Vec<u8> = Vec::new();
-    for chunk in coeffs.chunks(5) {
-        let mut padded = [0; 5];
-        padded[..chunk.len()].copy_from_slice(chunk);
-        match out_of_ternary(&padded) {
-            Some(c) => {
-                ret.push(c);
-            }
-            None => (),
-        }
+    // Convert every group of 3 bits to a pair of ternary digits
+    let bits: Vec<u8> = bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+        .collect();
+    let mut coeffs = Vec::with_capacity(bits.len().div_ceil(3) * 2);
+    for group in bits.chunks(3) {
+        let v = group
+            .iter()
+            .chain([0, 0].iter())
+            .take(3)
+            .fold(0, |acc, &bit| (acc << 1) | bit as i32);
+        coeffs.push(to_trit(v / 3));
+        coeffs.push(to_trit(v % 3));
     }
-    ret
+
+    Ok(ConvPoly { coeffs })
 }
 
-/// Takes a balanced ternary number in the form of an array and converts it to
-/// a decimal u8 (aka a char)
-/// Returns None if given a non valid char encoding
-fn out_of_ternary(ser_ch: &[i32]) -> Option<u8> {
-    if ser_ch == [0; 5] {
-        return None;
+/// Deserializes a convolution polynomial into the message it represents as a vector of u8s. The coefficients may be
+/// given in any representative modulo p. Every coefficient up to x^(N-1) is decoded, missing ones as 0, without
+/// branching on its value, and whether the encoding is valid is only checked once at the end, so that the time this
+/// takes doesn't depend on a decrypted message. Returns an error if the polynomial doesn't represent a valid message.
+pub fn deserialize(ser_msg: ConvPoly, params: &NtruParams) -> Result<Vec<u8>, NtruError> {
+    check_ternary_modulus(params)?;
+
+    let mut coeffs = constant_time::center_lift(&ser_msg.coeffs, params.p)?;
+    coeffs.resize(coeffs.len().max(params.n).next_multiple_of(2), 0);
+
+    // Convert every pair of ternary digits back to a group of 3 bits, noting invalid ones in a mask
+    let mut invalid = 0;
+    let mut bits = Vec::with_capacity(coeffs.len() / 2 * 3);
+    for pair in coeffs.chunks_exact(2) {
+        let v = from_trit(pair[0], &mut invalid) * 3 + from_trit(pair[1], &mut invalid);
+        // The pair (-1, -1) would represent 8
+        invalid |= v >> 3;
+        bits.extend((0..3).rev().map(|i| ((v >> i) & 1) as u8));
     }
 
-    const POWERS: [i32; 5] = [1, 3, 9, 27, 81];
+    bits.resize(bits.len().div_ceil(8) * 8, 0);
+    let mut bytes: Vec<u8> = bits
+        .chunks_exact(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| (acc << 1) | bit))
+        .collect();
+    let max = max_message_len(params);
+    bytes.resize(bytes.len().max(LENGTH_BYTES + max), 0);
+    let len = u16::from_be_bytes([bytes[0], bytes[1]]) as usize;
+    invalid |= negative_mask(max as i64 - len as i64);
+
+    if invalid != 0 {
+        return Err(NtruError::InvalidEncoding(
+            "polynomial has a coefficient that isn't ternary, the trit pair (-1, -1), or a length prefix exceeding \
+             what fits within a single polynomial",
+        ));
+    }
+    Ok(bytes[LENGTH_BYTES..LENGTH_BYTES + len].to_vec())
+}
 
-    let mut ans = 0;
-    // We know every balanced ternary number will be 5 indices, so here's a fun little
-    // constant time deserialization :)
-    ans += bal_tern_esc(ser_ch[4], POWERS[0]);
-    ans += bal_tern_esc(ser_ch[3], POWERS[1]);
-    ans += bal_tern_esc(ser_ch[2], POWERS[2]);
-    ans += bal_tern_esc(ser_ch[1], POWERS[3]);
-    ans += bal_tern_esc(ser_ch[0], POWERS[4]);
-    // If value is for some reason not a u8, returns None
-    match u8::try_from(ans) {
-        Ok(a) => Some(a),
-        Err(_) => {
-            eprintln!(
-                "out_of_ternary: Shouldn't try to deserialize a non u8 representable character"
-            );
-            None
-        }
+/// Returns an error if the small modulus p can't distinguish the ternary coefficients -1, 0 and 1
+fn check_ternary_modulus(params: &NtruParams) -> Result<(), NtruError> {
+    if params.p < 3 {
+        return Err(NtruError::InvalidParameters(
+            "message encoding requires p >= 3 to hold ternary coefficients",
+        ));
     }
+    Ok(())
 }
 
-/// Takes a value from an array representing a balanced ternary number
-/// and converts it to a decimal component of the decimal conversion,
-/// dependent on its position in the array and index value.
-fn bal_tern_esc(n: i32, exp: i32) -> i32 {
-    if n == -1 {
-        2 * exp
+/// Converts a digit in [0, 3) to a ternary coefficient, storing 2 as -1
+fn to_trit(digit: i32) -> i32 {
+    if digit == 2 {
+        -1
     } else {
-        n * exp
+        digit
     }
 }
+
+/// Converts a center-lifted coefficient back to a digit in [0, 3), without branching on it. Sets every bit of
+/// `invalid` if the coefficient isn't ternary.
+fn from_trit(coeff: i32, invalid: &mut i64) -> i64 {
+    let c = coeff as i64;
+    *invalid |= negative_mask(c + 1) | negative_mask(1 - c);
+    c + (3 & negative_mask(c))
+}
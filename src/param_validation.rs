@@ -0,0 +1,280 @@
+use crate::convolution_polynomial::{factorize, gcd, is_prime};
+use crate::params::NtruParams;
+use crate::ring_factorization::factor_degrees;
+use std::fmt;
+
+/// The outcome of checking a single condition on a parameter set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// The condition holds
+    Pass,
+    /// The condition does not hold, so keys or ciphertexts over the parameter set cannot be relied upon
+    Fail,
+    /// The condition does not hold in the worst case, so the scheme only works with high probability
+    Probabilistic,
+    /// The condition does not hold, which weakens the scheme without keeping it from working
+    Warning,
+}
+
+/// A condition that the NTRU encryption scheme relies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamCheck {
+    /// gcd(p, q) = 1, so that messages reduced modulo p survive the arithmetic modulo q
+    CoprimeModuli,
+    /// N is prime, so that x^N - 1 has no small factors other than x - 1
+    PrimeN,
+    /// 2d + 1 <= N, so that f(x) in T(d + 1, d) fits within N coefficients
+    WeightFitsRing,
+    /// q > (6d + 1)p, so that every coefficient of p*r(x)*g(x) + f(x)*m(x) lies within (-q/2, q/2]
+    DecryptionBound,
+    /// Modulo p and every prime dividing q, (x^N - 1)/(x - 1) only has irreducible factors of degree at least
+    /// (N - 1)/2, so that the ring doesn't split into many small pieces that keys and ciphertexts can be studied in
+    RingSplitting,
+}
+
+impl fmt::Display for ParamCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            ParamCheck::CoprimeModuli => "gcd(p, q) = 1",
+            ParamCheck::PrimeN => "N is prime",
+            ParamCheck::WeightFitsRing => "2d + 1 <= N",
+            ParamCheck::DecryptionBound => "q > (6d + 1)p",
+            ParamCheck::RingSplitting => "(x^N - 1)/(x - 1) has no small factors modulo p and q",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// The result of checking a single condition on a parameter set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// The condition that was checked
+    pub check: ParamCheck,
+    /// Whether the condition holds
+    pub status: CheckStatus,
+    /// A human-readable explanation of the outcome, including the values involved
+    pub detail: String,
+}
+
+/// A report on which of the conditions the NTRU encryption scheme relies on hold for a parameter set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The parameter set the report is about
+    pub params: NtruParams,
+    /// The result of every check, in the order they were performed
+    pub checks: Vec<CheckResult>,
+}
+
+impl ValidationReport {
+    /// Returns the status of the given check, if it was performed
+    pub fn status(&self, check: ParamCheck) -> Option<CheckStatus> {
+        self.checks
+            .iter()
+            .find(|result| result.check == check)
+            .map(|result| result.status)
+    }
+
+    /// Returns whether no check failed, i.e. whether the parameter set is usable (possibly with a small
+    /// probability of decryption failure)
+    pub fn is_valid(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|result| result.status != CheckStatus::Fail)
+    }
+
+    /// Returns whether every check passed or only warned, i.e. whether decryption is guaranteed to succeed
+    pub fn is_correct(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|result| matches!(result.status, CheckStatus::Pass | CheckStatus::Warning))
+    }
+}
+
+/// Display implementation for validation reports. Each check is displayed on its own line in the form
+/// "\[STATUS\] condition: detail".
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let NtruParams { n, p, q, d } = self.params;
+        writeln!(
+            f,
+            "NTRU parameters N = {}, p = {}, q = {}, d = {}",
+            n, p, q, d
+        )?;
+        for result in &self.checks {
+            let status = match result.status {
+                CheckStatus::Pass => "PASS",
+                CheckStatus::Fail => "FAIL",
+                CheckStatus::Probabilistic => "PROBABILISTIC",
+                CheckStatus::Warning => "WARNING",
+            };
+            writeln!(f, "[{}] {}: {}", status, result.check, result.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the conditions the NTRU encryption scheme relies on for the given parameter set and reports which of
+/// them pass, fail, only hold probabilistically or warrant a warning.
+pub fn validate(params: &NtruParams) -> ValidationReport {
+    let checks = vec![
+        check_coprime_moduli(params),
+        check_prime_n(params),
+        check_weight_fits_ring(params),
+        check_decryption_bound(params),
+        check_ring_splitting(params),
+    ];
+
+    ValidationReport {
+        params: *params,
+        checks,
+    }
+}
+
+fn check_coprime_moduli(params: &NtruParams) -> CheckResult {
+    let NtruParams { p, q, .. } = *params;
+    let (status, detail) = if p < 2 || q < 2 {
+        (
+            CheckStatus::Fail,
+            format!("both moduli must be at least 2 (p = {}, q = {})", p, q),
+        )
+    } else {
+        // Both moduli are non-zero, so the gcd always exists
+        let divisor = gcd(p, q).unwrap_or(0);
+        let status = if divisor == 1 {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        };
+        (status, format!("gcd({}, {}) = {}", p, q, divisor))
+    };
+
+    CheckResult {
+        check: ParamCheck::CoprimeModuli,
+        status,
+        detail,
+    }
+}
+
+fn check_prime_n(params: &NtruParams) -> CheckResult {
+    let n = params.n;
+    let prime = i32::try_from(n).map(is_prime).unwrap_or(false);
+    let (status, detail) = if prime {
+        (CheckStatus::Pass, format!("{} is prime", n))
+    } else {
+        (CheckStatus::Fail, format!("{} is not prime", n))
+    };
+
+    CheckResult {
+        check: ParamCheck::PrimeN,
+        status,
+        detail,
+    }
+}
+
+fn check_weight_fits_ring(params: &NtruParams) -> CheckResult {
+    let NtruParams { n, d, .. } = *params;
+    let weight = d.saturating_mul(2).saturating_add(1);
+    let status = if weight <= n {
+        CheckStatus::Pass
+    } else {
+        CheckStatus::Fail
+    };
+
+    CheckResult {
+        check: ParamCheck::WeightFitsRing,
+        status,
+        detail: format!("2d + 1 = {}, N = {}", weight, n),
+    }
+}
+
+fn check_decryption_bound(params: &NtruParams) -> CheckResult {
+    let NtruParams { p, q, d, .. } = *params;
+    // Computed in i64 so that large weights can't overflow the bound
+    let bound = (d as i64)
+        .saturating_mul(6)
+        .saturating_add(1)
+        .saturating_mul(p as i64);
+    let (status, detail) = if (q as i64) > bound {
+        (
+            CheckStatus::Pass,
+            format!("q = {} > {}, so decryption always succeeds", q, bound),
+        )
+    } else {
+        (
+            CheckStatus::Probabilistic,
+            format!(
+                "q = {} <= {}, so decryption succeeds only with high probability",
+                q, bound
+            ),
+        )
+    };
+
+    CheckResult {
+        check: ParamCheck::DecryptionBound,
+        status,
+        detail,
+    }
+}
+
+fn check_ring_splitting(params: &NtruParams) -> CheckResult {
+    let NtruParams { n, p, q, .. } = *params;
+    let mut primes: Vec<i32> = factorize(p)
+        .into_iter()
+        .chain(factorize(q))
+        .map(|(r, _)| r)
+        .collect();
+    primes.sort_unstable();
+    primes.dedup();
+
+    let mut status = CheckStatus::Pass;
+    let mut descriptions = Vec::new();
+    for r in primes {
+        let Ok(mut degrees) = factor_degrees(n, r) else {
+            status = CheckStatus::Fail;
+            descriptions.push("N must be positive".to_string());
+            break;
+        };
+        // x - 1 always divides x^N - 1, and is always its smallest factor; it's the others that matter
+        degrees.remove(0);
+        let repeated = n.is_multiple_of(r as usize);
+        if repeated || degrees.iter().any(|&k| 2 * k < n - 1) {
+            status = CheckStatus::Warning;
+        }
+
+        let mut groups: Vec<(usize, usize)> = Vec::new();
+        for k in degrees {
+            match groups.last_mut() {
+                Some((degree, count)) if *degree == k => *count += 1,
+                _ => groups.push((k, 1)),
+            }
+        }
+        let groups: Vec<String> = groups
+            .into_iter()
+            .map(|(k, count)| {
+                let plural = if count == 1 { "" } else { "s" };
+                format!("{} factor{} of degree {}", count, plural, k)
+            })
+            .collect();
+        let groups = if groups.is_empty() {
+            "no factors besides x - 1".to_string()
+        } else {
+            groups.join(" and ")
+        };
+        let repeated = if repeated {
+            let mut multiplicity = 1;
+            while n.is_multiple_of(multiplicity * r as usize) {
+                multiplicity *= r as usize;
+            }
+            format!(" (every factor repeated {} times)", multiplicity)
+        } else {
+            String::new()
+        };
+        descriptions.push(format!("modulo {}, {}{}", r, groups, repeated));
+    }
+
+    CheckResult {
+        check: ParamCheck::RingSplitting,
+        status,
+        detail: descriptions.join("; "),
+    }
+}
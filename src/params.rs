@@ -1,5 +1,221 @@
+use crate::compact_poly::{MAX_MODULUS_P, MAX_MODULUS_Q};
+use crate::error::NtruError;
+use crate::param_validation::{validate, ValidationReport};
+
 // NTRU Parameters, derived by Tanish and Alex
 pub const N: usize = 661;
 pub const P: i32 = 3;
 pub const Q: i32 = 383;
 pub const D: usize = 21;
+
+/// The parameter set derived by Tanish and Alex, used whenever no other set is requested.
+pub const DEFAULT_PARAMS: NtruParams = NtruParams::new(N, P, Q, D);
+
+/// A set of NTRU parameters. Keys store the parameter set they were generated with, so a single process
+/// can hold keys of several different security levels at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NtruParams {
+    /// The number of coefficients of each polynomial; operations take place in the ring Z\[x\]/(x^n - 1)
+    pub n: usize,
+    /// The small modulus, which message coefficients are reduced by
+    pub p: i32,
+    /// The large modulus, which public key and ciphertext coefficients are reduced by
+    pub q: i32,
+    /// The weight parameter. The private f(x) is drawn from T(d + 1, d), while g(x) and the random
+    /// perturbation r(x) are drawn from T(d, d)
+    pub d: usize,
+}
+
+impl NtruParams {
+    /// Constructs a parameter set from its ring size `n`, moduli `p` and `q`, and weight parameter `d`.
+    pub const fn new(n: usize, p: i32, q: i32, d: usize) -> NtruParams {
+        NtruParams { n, p, q, d }
+    }
+
+    /// Checks the conditions the NTRU encryption scheme relies on for this parameter set. See
+    /// [`crate::param_validation::validate`].
+    pub fn validate(&self) -> ValidationReport {
+        validate(self)
+    }
+
+    /// Returns an error if the parameter set can't be used to generate keys or encrypt at all. Unlike
+    /// [`NtruParams::validate`], this only rules out parameters that would make the scheme's arithmetic meaningless.
+    pub(crate) fn ensure_usable(&self) -> Result<(), NtruError> {
+        if self.n == 0 {
+            return Err(NtruError::InvalidParameters("N must be positive"));
+        }
+        if self.p < 2 || self.q < 2 {
+            return Err(NtruError::InvalidParameters(
+                "moduli p and q must be at least 2",
+            ));
+        }
+        if self.q > MAX_MODULUS_Q {
+            return Err(NtruError::InvalidParameters(
+                "q must be at most 2^16 for keys and ciphertexts to be stored with 16-bit coefficients",
+            ));
+        }
+        if self.p > MAX_MODULUS_P {
+            return Err(NtruError::InvalidParameters(
+                "p must be at most 255 for f_p(x) to be stored with 8-bit coefficients",
+            ));
+        }
+        if self.d.saturating_mul(2).saturating_add(1) > self.n {
+            return Err(NtruError::InvalidParameters(
+                "f(x) in T(d + 1, d) must fit within N coefficients",
+            ));
+        }
+        // With 2d + 1 = N every coefficient of f(x) is ±1, so f(x) ≡ 1 + x + ... + x^(N-1) (mod 2), which divides x^N - 1
+        if self.n > 1 && 2 * self.d + 1 == self.n && (self.p % 2 == 0 || self.q % 2 == 0) {
+            return Err(NtruError::InvalidParameters(
+                "f(x) in T(d + 1, d) with 2d + 1 = N is never invertible modulo an even p or q",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for NtruParams {
+    fn default() -> NtruParams {
+        DEFAULT_PARAMS
+    }
+}
+
+/// The weights of a product-form private key f(x) = 1 + p*(f1(x)*f2(x) + f3(x)), where each fi(x) is drawn from
+/// T(di, di). Keeping them small makes decryption a few sparse convolutions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProductFormWeights {
+    /// The weight of f1(x)
+    pub d1: usize,
+    /// The weight of f2(x)
+    pub d2: usize,
+    /// The weight of f3(x)
+    pub d3: usize,
+}
+
+impl ProductFormWeights {
+    /// Constructs the weights of the factors f1(x), f2(x) and f3(x)
+    pub const fn new(d1: usize, d2: usize, d3: usize) -> ProductFormWeights {
+        ProductFormWeights { d1, d2, d3 }
+    }
+
+    /// Returns an error if any factor in T(di, di) doesn't fit within N coefficients
+    pub(crate) fn ensure_usable(&self, params: &NtruParams) -> Result<(), NtruError> {
+        if [self.d1, self.d2, self.d3]
+            .iter()
+            .any(|d| d.saturating_mul(2) > params.n)
+        {
+            return Err(NtruError::InvalidParameters(
+                "product-form factors in T(di, di) must fit within N coefficients",
+            ));
+        }
+        Ok(())
+    }
+}
+
+// STANDARDIZED PARAMETER SETS
+//
+// Each preset takes its ring size and moduli from the standard it is named after, but this crate's scheme has a single
+// weight parameter d, with f(x) in T(d+1, d) and g(x) in T(d, d), so the presets only approximate the standards' key
+// distributions. d is chosen to match the standard's weight for g(x): dg for the IEEE 1363.1 / EESS#1 sets and
+// q/16 - 1 for the NIST round-3 HPS sets. EESS#1 draws f(x) = 1 + pF(x) with a product-form F(x) of its own weights,
+// HPS draws f(x) as an arbitrary ternary polynomial, and NTRU-HRSS draws both without a fixed weight and works modulo
+// Φ_N(x) rather than x^N - 1, so its preset uses the expected weight of a uniform ternary polynomial, d ≈ N/3. The
+// presets therefore don't carry the security levels claimed for the standards, and they're used within this crate's
+// scheme and are not wire-compatible with other implementations of the standards.
+
+/// IEEE 1363.1 / EESS#1 ees401ep1
+pub const EES401EP1: NtruParams = NtruParams::new(401, 3, 2048, 133);
+/// IEEE 1363.1 / EESS#1 ees443ep1
+pub const EES443EP1: NtruParams = NtruParams::new(443, 3, 2048, 148);
+/// IEEE 1363.1 / EESS#1 ees587ep1
+pub const EES587EP1: NtruParams = NtruParams::new(587, 3, 2048, 196);
+/// IEEE 1363.1 / EESS#1 ees743ep1
+pub const EES743EP1: NtruParams = NtruParams::new(743, 3, 2048, 247);
+/// NIST round-3 ntruhps2048509
+pub const NTRUHPS2048509: NtruParams = NtruParams::new(509, 3, 2048, 127);
+/// NIST round-3 ntruhps2048677
+pub const NTRUHPS2048677: NtruParams = NtruParams::new(677, 3, 2048, 127);
+/// NIST round-3 ntruhps4096821
+pub const NTRUHPS4096821: NtruParams = NtruParams::new(821, 3, 4096, 255);
+/// NIST round-3 ntruhrss701
+pub const NTRUHRSS701: NtruParams = NtruParams::new(701, 3, 8192, 233);
+
+/// A named NTRU parameter set approximating a standardized one, whose security level it doesn't carry. Presets can be
+/// looked up by name (e.g. "ees401ep1") or by their numeric ID, which is stable and suitable for sending over the
+/// wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamSet {
+    Ees401Ep1 = 1,
+    Ees443Ep1 = 2,
+    Ees587Ep1 = 3,
+    Ees743Ep1 = 4,
+    NtruHps2048509 = 5,
+    NtruHps2048677 = 6,
+    NtruHps4096821 = 7,
+    NtruHrss701 = 8,
+}
+
+impl ParamSet {
+    /// Every available preset, in order of ID
+    pub const ALL: [ParamSet; 8] = [
+        ParamSet::Ees401Ep1,
+        ParamSet::Ees443Ep1,
+        ParamSet::Ees587Ep1,
+        ParamSet::Ees743Ep1,
+        ParamSet::NtruHps2048509,
+        ParamSet::NtruHps2048677,
+        ParamSet::NtruHps4096821,
+        ParamSet::NtruHrss701,
+    ];
+
+    /// Returns the standardized name of the preset
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParamSet::Ees401Ep1 => "ees401ep1",
+            ParamSet::Ees443Ep1 => "ees443ep1",
+            ParamSet::Ees587Ep1 => "ees587ep1",
+            ParamSet::Ees743Ep1 => "ees743ep1",
+            ParamSet::NtruHps2048509 => "ntruhps2048509",
+            ParamSet::NtruHps2048677 => "ntruhps2048677",
+            ParamSet::NtruHps4096821 => "ntruhps4096821",
+            ParamSet::NtruHrss701 => "ntruhrss701",
+        }
+    }
+
+    /// Returns the numeric ID of the preset
+    pub fn id(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Looks up a preset by its name, ignoring case. Returns None if no preset has that name.
+    pub fn from_name(name: &str) -> Option<ParamSet> {
+        ParamSet::ALL
+            .into_iter()
+            .find(|set| set.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Looks up a preset by its numeric ID. Returns None if no preset has that ID.
+    pub fn from_id(id: u8) -> Option<ParamSet> {
+        ParamSet::ALL.into_iter().find(|set| set.id() == id)
+    }
+
+    /// Returns the parameters of the preset
+    pub fn params(&self) -> NtruParams {
+        match self {
+            ParamSet::Ees401Ep1 => EES401EP1,
+            ParamSet::Ees443Ep1 => EES443EP1,
+            ParamSet::Ees587Ep1 => EES587EP1,
+            ParamSet::Ees743Ep1 => EES743EP1,
+            ParamSet::NtruHps2048509 => NTRUHPS2048509,
+            ParamSet::NtruHps2048677 => NTRUHPS2048677,
+            ParamSet::NtruHps4096821 => NTRUHPS4096821,
+            ParamSet::NtruHrss701 => NTRUHRSS701,
+        }
+    }
+}
+
+impl From<ParamSet> for NtruParams {
+    fn from(set: ParamSet) -> NtruParams {
+        set.params()
+    }
+}
@@ -0,0 +1,616 @@
+use crate::constant_time::{self, inverse_mod_prime};
+use crate::convolution_polynomial::{factorize, inverse, is_prime, ternary_polynomial, ConvPoly};
+use crate::error::NtruError;
+use crate::multiplication::{polynomial_product, MulBackend};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::Arc;
+
+// A ConvPoly is just a list of integers, so every operation on one has to be told which ring it is happening in.
+// A PolyRing fixes both the reduction polynomial and the coefficient modulus, and the RingElements it creates carry it
+// with them: their coefficient vectors always have exactly as many entries as the degree of the reduction polynomial,
+// reduced into [0, modulus), and elements of different rings can't be combined. The arithmetic operators panic when
+// given elements of different rings, just like indexing out of bounds does; the try_* methods return an error instead.
+//
+// NTRU's keys and ciphertexts live in the convolution ring modulo x^N - 1, but NTRU-HPS and NTRU-HRSS also work modulo
+// Φ_N = (x^N - 1)/(x - 1), and other lattice schemes modulo x^N + 1. Products are computed over the integers by any
+// multiplication backend and then reduced by the ring's polynomial, for which the three common choices have shortcuts.
+
+/// The monic polynomial a PolyRing's elements are reduced by
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReductionPoly {
+    /// x^N - 1, the convolution ring NTRU's keys and ciphertexts live in
+    Cyclic(usize),
+    /// x^N + 1, the negacyclic ring
+    Negacyclic(usize),
+    /// Φ_N(x) = (x^N - 1)/(x - 1) = x^(N - 1) + ... + x + 1, the N-th cyclotomic polynomial when N is prime
+    Cyclotomic(usize),
+    /// An arbitrary monic polynomial, given by its coefficients from the constant term up to the leading 1
+    Monic(Arc<[i32]>),
+}
+
+impl ReductionPoly {
+    /// Returns the degree of the polynomial, which is the number of coefficients of every element of a ring reduced by
+    /// it
+    pub fn degree(&self) -> usize {
+        match self {
+            ReductionPoly::Cyclic(n) | ReductionPoly::Negacyclic(n) => *n,
+            ReductionPoly::Cyclotomic(n) => n.saturating_sub(1),
+            ReductionPoly::Monic(coeffs) => coeffs.len().saturating_sub(1),
+        }
+    }
+
+    /// Returns the coefficients of the polynomial from the constant term up to the leading 1
+    pub fn coeffs(&self) -> Vec<i32> {
+        let d = self.degree();
+        let mut coeffs = vec![0; d + 1];
+        match self {
+            ReductionPoly::Cyclic(_) => (coeffs[0], coeffs[d]) = (-1, 1),
+            ReductionPoly::Negacyclic(_) => (coeffs[0], coeffs[d]) = (1, 1),
+            ReductionPoly::Cyclotomic(_) => coeffs.fill(1),
+            ReductionPoly::Monic(monic) => coeffs.copy_from_slice(monic),
+        }
+        coeffs
+    }
+
+    /// Returns the cyclotomic polynomials Φ_d(x) for every divisor d of n in increasing order, whose product is
+    /// x^n - 1. Each is found by dividing x^d - 1 by the factors before it.
+    pub fn cyclotomic_factors(n: usize) -> Vec<ReductionPoly> {
+        let mut found: Vec<(usize, Vec<i32>)> = Vec::new();
+        for d in (1..=n).filter(|&d| n.is_multiple_of(d)) {
+            let mut rest = vec![0; d + 1];
+            (rest[0], rest[d]) = (-1, 1);
+            for (_, factor) in found.iter().filter(|(e, _)| d.is_multiple_of(*e)) {
+                // Exact division by a monic polynomial over Z, from the top down
+                let k = factor.len() - 1;
+                let mut quotient = vec![0; rest.len() - k];
+                for i in (0..quotient.len()).rev() {
+                    quotient[i] = rest[i + k];
+                    for (j, &c) in factor.iter().enumerate() {
+                        rest[i + j] -= quotient[i] * c;
+                    }
+                }
+                rest = quotient;
+            }
+            found.push((d, rest));
+        }
+
+        found
+            .into_iter()
+            .map(|(d, coeffs)| match d {
+                // For a prime d, Φ_d(x) = (x^d - 1)/(x - 1)
+                d if d > 1 && is_prime(d as i32) => ReductionPoly::Cyclotomic(d),
+                _ => ReductionPoly::Monic(coeffs.into()),
+            })
+            .collect()
+    }
+
+    /// Returns an error if the polynomial isn't monic or has degree 0
+    pub(crate) fn check(&self) -> Result<(), NtruError> {
+        match self {
+            ReductionPoly::Cyclic(0) | ReductionPoly::Negacyclic(0) => {
+                Err(NtruError::InvalidParameters("N must be positive"))
+            }
+            ReductionPoly::Cyclotomic(n) if *n < 2 => Err(NtruError::InvalidParameters(
+                "N must be at least 2 for Φ_N to have positive degree",
+            )),
+            ReductionPoly::Monic(coeffs) if coeffs.len() < 2 || coeffs.last() != Some(&1) => {
+                Err(NtruError::InvalidParameters(
+                    "reduction polynomial must be monic with positive degree",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reduces a polynomial over Z, given by its coefficients, modulo this polynomial and m. The result has exactly
+    /// as many coefficients as the degree of this polynomial, each within \[0, m).
+    fn reduce(&self, coeffs: &[i128], m: i32) -> Vec<i32> {
+        let d = self.degree();
+        let m = m as i128;
+        let folded = match self {
+            // x^N = 1, so the coefficient of x^i is added onto x^(i mod N)
+            ReductionPoly::Cyclic(n) => fold(coeffs, *n, 1),
+            // x^N = -1, so it's added or subtracted depending on how many times it wraps around
+            ReductionPoly::Negacyclic(n) => fold(coeffs, *n, -1),
+            // Φ_N divides x^N - 1, and x^(N - 1) = -(x^(N - 2) + ... + x + 1)
+            ReductionPoly::Cyclotomic(n) => {
+                let cyclic = fold(coeffs, *n, 1);
+                cyclic[..d].iter().map(|c| c - cyclic[d]).collect()
+            }
+            // Long division by the monic polynomial, cancelling the leading term from the top down
+            ReductionPoly::Monic(monic) => {
+                let low: Vec<i128> = monic[..d]
+                    .iter()
+                    .map(|&c| (c as i128).rem_euclid(m))
+                    .collect();
+                let mut rest: Vec<i128> = coeffs.iter().map(|c| c.rem_euclid(m)).collect();
+                rest.resize(rest.len().max(d), 0);
+                for i in (d..rest.len()).rev() {
+                    let lead = rest[i];
+                    for (sum, &c) in rest[i - d..i].iter_mut().zip(&low) {
+                        *sum = (*sum - lead * c).rem_euclid(m);
+                    }
+                }
+                rest.truncate(d);
+                rest
+            }
+        };
+        folded.into_iter().map(|c| c.rem_euclid(m) as i32).collect()
+    }
+}
+
+/// Display implementation for reduction polynomials, e.g. "x^N - 1" or "Φ_N"
+impl fmt::Display for ReductionPoly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReductionPoly::Cyclic(n) => write!(f, "x^{} - 1", n),
+            ReductionPoly::Negacyclic(n) => write!(f, "x^{} + 1", n),
+            ReductionPoly::Cyclotomic(n) => write!(f, "Φ_{}", n),
+            ReductionPoly::Monic(_) => write!(
+                f,
+                "{}",
+                ConvPoly {
+                    coeffs: self.coeffs()
+                }
+            ),
+        }
+    }
+}
+
+/// Folds the coefficient of x^i onto x^(i mod n), multiplied by `wrap` for each time it wraps around
+fn fold(coeffs: &[i128], n: usize, wrap: i128) -> Vec<i128> {
+    let mut result = vec![0; n];
+    for (chunk, sign) in coeffs
+        .chunks(n)
+        .zip(std::iter::successors(Some(1), |s| Some(s * wrap)))
+    {
+        for (sum, &c) in result.iter_mut().zip(chunk) {
+            *sum += sign * c;
+        }
+    }
+    result
+}
+
+/// The ring of polynomials (Z/mZ)\[x\]/(f(x)) for a fixed reduction polynomial f(x) and coefficient modulus m
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolyRing {
+    /// The number of coefficients of every element, which is the degree of the reduction polynomial
+    n: usize,
+    /// The modulus every coefficient is reduced by
+    modulus: i32,
+    /// The polynomial every element is reduced by
+    reduction: ReductionPoly,
+}
+
+impl PolyRing {
+    /// Constructs the convolution ring (Z/mZ)\[x\]/(x^n - 1). Returns an error if `n` is 0 or the modulus is not a
+    /// positive integer.
+    pub fn new(n: usize, modulus: i32) -> Result<PolyRing, NtruError> {
+        PolyRing::with_reduction(ReductionPoly::Cyclic(n), modulus)
+    }
+
+    /// Constructs the ring (Z/mZ)\[x\]/(f(x)) for the given reduction polynomial f(x). Returns an error if f(x) isn't
+    /// monic with positive degree or the modulus is not a positive integer.
+    pub fn with_reduction(reduction: ReductionPoly, modulus: i32) -> Result<PolyRing, NtruError> {
+        reduction.check()?;
+        if modulus <= 0 {
+            return Err(NtruError::InvalidModulus(modulus));
+        }
+        Ok(PolyRing {
+            n: reduction.degree(),
+            modulus,
+            reduction,
+        })
+    }
+
+    /// Returns the number of coefficients N of every element of the ring, which is the degree of its reduction
+    /// polynomial
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the coefficient modulus of the ring
+    pub fn modulus(&self) -> i32 {
+        self.modulus
+    }
+
+    /// Returns the polynomial every element of the ring is reduced by
+    pub fn reduction(&self) -> &ReductionPoly {
+        &self.reduction
+    }
+
+    /// Returns the zero element of the ring
+    pub fn zero(&self) -> RingElement {
+        RingElement {
+            ring: self.clone(),
+            coeffs: vec![0; self.n],
+        }
+    }
+
+    /// Returns the constant element c of the ring
+    pub fn constant(&self, c: i32) -> RingElement {
+        self.element(&ConvPoly::constant(c))
+    }
+
+    /// Maps a polynomial into the ring. Coefficients of x^i with i >= N are reduced by the ring's polynomial (in the
+    /// convolution ring, x^i wraps around onto x^(i mod N), since x^N = 1), and every coefficient is reduced into
+    /// \[0, modulus).
+    pub fn element(&self, poly: &ConvPoly) -> RingElement {
+        let coeffs: Vec<i128> = poly.coeffs.iter().map(|&c| c as i128).collect();
+        RingElement {
+            ring: self.clone(),
+            coeffs: self.reduction.reduce(&coeffs, self.modulus),
+        }
+    }
+
+    /// Maps exactly N coefficients into the ring, reducing each one into \[0, modulus) in a time that doesn't depend on
+    /// them, so that it can be used on secret keys and messages. Returns an error if there aren't N coefficients.
+    pub fn element_constant_time<T: Copy + Into<i64>>(
+        &self,
+        coeffs: &[T],
+    ) -> Result<RingElement, NtruError> {
+        if coeffs.len() != self.n {
+            return Err(NtruError::ParameterMismatch(
+                "polynomial doesn't have N coefficients",
+            ));
+        }
+        Ok(RingElement {
+            ring: self.clone(),
+            coeffs: constant_time::modulo(coeffs, self.modulus)?,
+        })
+    }
+
+    /// Generates a random ternary element of the ring with `num_ones` 1s and `num_neg_ones` -1s. Returns an error if
+    /// there are more 1s and -1s than coefficients.
+    pub fn ternary(&self, num_ones: usize, num_neg_ones: usize) -> Result<RingElement, NtruError> {
+        Ok(self.element(&ternary_polynomial(self.n, num_ones, num_neg_ones)?))
+    }
+
+    /// Returns the same ring with a different coefficient modulus
+    fn with_modulus(&self, modulus: i32) -> PolyRing {
+        PolyRing {
+            modulus,
+            ..self.clone()
+        }
+    }
+}
+
+/// Display implementation for rings, in the form "(Z/mZ)\[x\]/(x^N - 1)"
+impl fmt::Display for PolyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(Z/{}Z)[x]/({})", self.modulus, self.reduction)
+    }
+}
+
+/// An element of a PolyRing, stored as exactly N coefficients reduced into \[0, modulus)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingElement {
+    /// The ring the element belongs to
+    ring: PolyRing,
+    /// Coefficients of the element such that coeffs\[i\] is the coefficient of x^i
+    coeffs: Vec<i32>,
+}
+
+impl RingElement {
+    /// Returns the ring the element belongs to
+    pub fn ring(&self) -> &PolyRing {
+        &self.ring
+    }
+
+    /// Returns the N coefficients of the element, each within \[0, modulus)
+    pub fn coeffs(&self) -> &[i32] {
+        &self.coeffs
+    }
+
+    /// Returns whether the element is zero
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&c| c == 0)
+    }
+
+    /// Returns the element as a polynomial with coefficients in \[0, modulus)
+    pub fn to_poly(&self) -> ConvPoly {
+        ConvPoly {
+            coeffs: self.coeffs.clone(),
+        }
+        .trim()
+    }
+
+    /// Lifts the element out of Z/mZ, keeping the same reduction polynomial, by center-lifting each coefficient into
+    /// (-m/2, m/2\]
+    pub fn center_lift(&self) -> ConvPoly {
+        ConvPoly {
+            coeffs: self.center_lift_constant_time(),
+        }
+        .trim()
+    }
+
+    /// Center-lifts every one of the N coefficients into (-m/2, m/2\], in a time that doesn't depend on them
+    pub fn center_lift_constant_time(&self) -> Vec<i32> {
+        // The ring's modulus is always positive
+        constant_time::center_lift(&self.coeffs, self.ring.modulus).unwrap_or_default()
+    }
+
+    /// Returns the sum of this element and another. Returns an error if they belong to different rings.
+    pub fn try_add(&self, other: &RingElement) -> Result<RingElement, NtruError> {
+        self.check_same_ring(other)?;
+        let m = self.ring.modulus as i64;
+        Ok(self.map_coeffs(|i, c| (c as i64 + other.coeffs[i] as i64).rem_euclid(m)))
+    }
+
+    /// Returns the difference of this element and another. Returns an error if they belong to different rings.
+    pub fn try_sub(&self, other: &RingElement) -> Result<RingElement, NtruError> {
+        self.check_same_ring(other)?;
+        let m = self.ring.modulus as i64;
+        Ok(self.map_coeffs(|i, c| (c as i64 - other.coeffs[i] as i64).rem_euclid(m)))
+    }
+
+    /// Returns the product of this element and another. Returns an error if they belong to different rings.
+    pub fn try_mul(&self, other: &RingElement) -> Result<RingElement, NtruError> {
+        self.try_mul_with(other, MulBackend::Auto)
+    }
+
+    /// Returns the product of this element and another, using the given multiplication backend. Returns an error if
+    /// they belong to different rings.
+    pub fn try_mul_with(
+        &self,
+        other: &RingElement,
+        backend: MulBackend,
+    ) -> Result<RingElement, NtruError> {
+        self.check_same_ring(other)?;
+        let product = polynomial_product(&self.coeffs, &other.coeffs, backend);
+        Ok(RingElement {
+            ring: self.ring.clone(),
+            coeffs: self.ring.reduction.reduce(&product, self.ring.modulus),
+        })
+    }
+
+    /// Returns the product of this element and another, working through every pair of coefficients in a time that
+    /// depends only on the ring, so that it can be used on secret keys and messages. Returns an error if they belong to
+    /// different rings.
+    pub fn mul_constant_time(&self, other: &RingElement) -> Result<RingElement, NtruError> {
+        self.check_same_ring(other)?;
+        Ok(RingElement {
+            ring: self.ring.clone(),
+            coeffs: constant_time::product(
+                &self.coeffs,
+                &other.coeffs,
+                &self.ring.reduction,
+                self.ring.modulus,
+            )?,
+        })
+    }
+
+    /// Returns the element multiplied by the integer c
+    pub fn scale(&self, c: i32) -> RingElement {
+        let m = self.ring.modulus as i64;
+        self.map_coeffs(|_, coeff| (coeff as i64 * c as i64).rem_euclid(m))
+    }
+
+    /// Computes the multiplicative inverse of the element. For prime and prime power moduli, the time this takes
+    /// depends only on the ring and not on the element; other moduli are inverted modulo each of their prime power
+    /// factors and recombined with the Chinese remainder theorem. Returns an error if the element is not invertible.
+    pub fn inverse(&self) -> Result<RingElement, NtruError> {
+        let modulus = self.ring.modulus;
+        let [(p, k)] = factorize(modulus)[..] else {
+            return self.crt_inverse();
+        };
+
+        // Invert modulo p, then Newton-lift b(x) <-- b(x)(2 - a(x)b(x)) within the full ring. Each step squares
+        // 1 - a(x)b(x), doubling the power of p that divides it. Every coefficient is passed on, zero or not, and
+        // reduced and multiplied in constant time, so that neither the degree nor the values of the element show.
+        let a: Vec<u64> = constant_time::modulo(&self.coeffs, p)?
+            .into_iter()
+            .map(|c| c as u64)
+            .collect();
+        let modulus_poly: Vec<u64> = self
+            .ring
+            .reduction
+            .coeffs()
+            .into_iter()
+            .map(|c| c.rem_euclid(p) as u64)
+            .collect();
+        let base =
+            inverse_mod_prime(&a, &modulus_poly, p as u64).ok_or(NtruError::NotInvertible)?;
+
+        let mut inverse = RingElement {
+            ring: self.ring.clone(),
+            coeffs: base.into_iter().map(|c| c as i32).collect(),
+        };
+        let mut e = 1;
+        while e < k {
+            let product = self.mul_constant_time(&inverse)?;
+            let correction: Vec<i64> = product
+                .coeffs
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| 2 * (i == 0) as i64 - c as i64)
+                .collect();
+            inverse = inverse.mul_constant_time(&self.ring.element_constant_time(&correction)?)?;
+            e *= 2;
+        }
+        Ok(inverse)
+    }
+
+    /// Returns whether the element is invertible, which is the case exactly when its resultant with the ring's
+    /// reduction polynomial is non-zero modulo every prime dividing the modulus. This is much cheaper than computing
+    /// the inverse.
+    pub fn is_invertible(&self) -> bool {
+        let poly = self.to_poly();
+        factorize(self.ring.modulus)
+            .into_iter()
+            .all(|(p, _)| matches!(poly.resultant_mod(&self.ring.reduction, p), Ok(r) if r != 0))
+    }
+
+    /// Inverts the element modulo each prime power factor p^k of the ring's modulus m, and recombines the inverses as
+    /// the sum of each one times the integer that is 1 modulo p^k and 0 modulo m/p^k. Like the inversion modulo each
+    /// factor, the recombination passes on every coefficient and reduces in constant time.
+    fn crt_inverse(&self) -> Result<RingElement, NtruError> {
+        let modulus = self.ring.modulus;
+        let mut result = vec![0i64; self.coeffs.len()];
+        for (p, k) in factorize(modulus) {
+            let prime_power = p.pow(k);
+            let component = self
+                .ring
+                .with_modulus(prime_power)
+                .element_constant_time(&self.coeffs)?
+                .inverse()?;
+            let cofactor = modulus / prime_power;
+            let idempotent =
+                cofactor as i64 * inverse(cofactor, prime_power)? as i64 % modulus as i64;
+            // Both terms are below m < 2^31, so the sum fits in an i64
+            let sum: Vec<i64> = result
+                .iter()
+                .zip(&component.coeffs)
+                .map(|(&r, &c)| r + c as i64 * idempotent)
+                .collect();
+            result = constant_time::modulo(&sum, modulus)?
+                .into_iter()
+                .map(i64::from)
+                .collect();
+        }
+        self.ring.element_constant_time(&result)
+    }
+
+    /// Returns an error if the other element belongs to a different ring
+    fn check_same_ring(&self, other: &RingElement) -> Result<(), NtruError> {
+        if self.ring != other.ring {
+            return Err(NtruError::RingMismatch);
+        }
+        Ok(())
+    }
+
+    /// Returns the element of the same ring whose i-th coefficient is f(i, coeffs\[i\]), which must already be reduced
+    fn map_coeffs(&self, f: impl Fn(usize, i32) -> i64) -> RingElement {
+        RingElement {
+            ring: self.ring.clone(),
+            coeffs: self
+                .coeffs
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| f(i, c) as i32)
+                .collect(),
+        }
+    }
+}
+
+/// Display implementation for ring elements, displayed the same way as the polynomial of their coefficients
+impl fmt::Display for RingElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_poly())
+    }
+}
+
+// ARITHMETIC OPERATORS
+
+/// Implements a binary operator and its compound assignment form for every combination of owned and borrowed
+/// ring elements in terms of the fallible `$try_method`, panicking if the elements belong to different rings
+macro_rules! impl_binary_op {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident, $try_method:ident) => {
+        impl $op<&RingElement> for &RingElement {
+            type Output = RingElement;
+
+            fn $method(self, rhs: &RingElement) -> RingElement {
+                self.$try_method(rhs)
+                    .unwrap_or_else(|err| panic!("{}: {} and {}", err, self.ring, rhs.ring))
+            }
+        }
+
+        impl $op<RingElement> for &RingElement {
+            type Output = RingElement;
+
+            fn $method(self, rhs: RingElement) -> RingElement {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $op<&RingElement> for RingElement {
+            type Output = RingElement;
+
+            fn $method(self, rhs: &RingElement) -> RingElement {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $op<RingElement> for RingElement {
+            type Output = RingElement;
+
+            fn $method(self, rhs: RingElement) -> RingElement {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $assign_op<&RingElement> for RingElement {
+            fn $assign_method(&mut self, rhs: &RingElement) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+
+        impl $assign_op<RingElement> for RingElement {
+            fn $assign_method(&mut self, rhs: RingElement) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign, try_add);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign, try_sub);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign, try_mul);
+
+/// Returns the additive inverse of the element
+impl Neg for &RingElement {
+    type Output = RingElement;
+
+    fn neg(self) -> RingElement {
+        let m = self.ring.modulus as i64;
+        self.map_coeffs(|_, c| (-(c as i64)).rem_euclid(m))
+    }
+}
+
+impl Neg for RingElement {
+    type Output = RingElement;
+
+    fn neg(self) -> RingElement {
+        -&self
+    }
+}
+
+/// Multiplies the element by an integer, so that e.g. p*r(x) can be written as `p * &r`
+impl Mul<i32> for &RingElement {
+    type Output = RingElement;
+
+    fn mul(self, rhs: i32) -> RingElement {
+        self.scale(rhs)
+    }
+}
+
+impl Mul<i32> for RingElement {
+    type Output = RingElement;
+
+    fn mul(self, rhs: i32) -> RingElement {
+        self.scale(rhs)
+    }
+}
+
+impl Mul<&RingElement> for i32 {
+    type Output = RingElement;
+
+    fn mul(self, rhs: &RingElement) -> RingElement {
+        rhs.scale(self)
+    }
+}
+
+impl Mul<RingElement> for i32 {
+    type Output = RingElement;
+
+    fn mul(self, rhs: RingElement) -> RingElement {
+        rhs.scale(self)
+    }
+}
+
+impl MulAssign<i32> for RingElement {
+    fn mul_assign(&mut self, rhs: i32) {
+        *self = self.scale(rhs);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::convolution_polynomial::{div_rem_mod_prime, gcd, is_prime, ConvPoly};
+use crate::error::NtruError;
+use crate::multiplication::{polynomial_product, pow_mod, MulBackend};
+use crate::poly_ring::ReductionPoly;
+use rand::Rng;
+use std::fmt;
+
+// Over F_p, x^N - 1 = (x^M - 1)^(p^e) where p^e is the largest power of p dividing N, and x^M - 1 is squarefree. Its
+// roots are the M-th roots of unity ζ^j in an extension of F_p, which the Frobenius map raises to the p-th power, so
+// the roots of each irreducible factor are the ζ^j for j in one cyclotomic coset {j, jp, jp^2, ...} modulo M. The
+// factor degrees are therefore just the sizes of the cosets, ord_M(p) for the coset of 1, and need no polynomial
+// arithmetic at all.
+//
+// To find the factors themselves, each cyclotomic polynomial Φ_d(x) with d | M is split by Cantor-Zassenhaus: all of
+// its irreducible factors have degree k = ord_d(p), and modulo each of them the trace h + h^p + ... + h^(p^(k-1)) of a
+// random h(x) is an element of F_p, so a gcd with (a power of) the trace separates factors whose traces differ.
+// Raising to the p-th power is cheap here: h(x)^p = h(x^p) over F_p, and x^p is just x^(p mod M) modulo x^M - 1.
+
+/// Returns the multiplicative order of `a` modulo `n`, the smallest k > 0 with a^k ≡ 1 (mod n), or None if `a` isn't
+/// coprime to `n` (or `n` is 0)
+pub fn multiplicative_order(a: i32, n: usize) -> Option<usize> {
+    let m = i32::try_from(n).ok().filter(|&m| m > 0)?;
+    if gcd(a, m) != Ok(1) {
+        return None;
+    }
+
+    let (a, n) = (a.rem_euclid(m) as u64, n as u64);
+    let (mut order, mut power) = (1, a % n);
+    while power != 1 % n {
+        power = power * a % n;
+        order += 1;
+    }
+    Some(order)
+}
+
+/// Returns the cyclotomic cosets {j, jp, jp^2, ...} of `p` modulo `n`, which partition 0, ..., n - 1, ordered by their
+/// smallest element. Returns None if `p` isn't coprime to `n` (or `n` is 0).
+pub fn cyclotomic_cosets(p: i32, n: usize) -> Option<Vec<Vec<usize>>> {
+    multiplicative_order(p, n)?;
+
+    let p = p.rem_euclid(n as i32) as usize;
+    let mut seen = vec![false; n];
+    let mut cosets = Vec::new();
+    for start in 0..n {
+        let mut coset = Vec::new();
+        let mut j = start;
+        while !seen[j] {
+            seen[j] = true;
+            coset.push(j);
+            j = j * p % n;
+        }
+        if !coset.is_empty() {
+            cosets.push(coset);
+        }
+    }
+    Some(cosets)
+}
+
+/// Returns the degrees of the distinct irreducible factors of x^n - 1 over F_p in increasing order, from the sizes of
+/// the cyclotomic cosets of p. Each factor divides x^n - 1 as many times as the largest power of p dividing n (see
+/// [`CyclicFactorization::multiplicity`]). Returns an error if `p` isn't prime or `n` is 0.
+pub fn factor_degrees(n: usize, p: i32) -> Result<Vec<usize>, NtruError> {
+    let (m, _) = split_multiplicity(n, p)?;
+    let mut degrees: Vec<usize> = cyclotomic_cosets(p, m)
+        .unwrap_or_default()
+        .iter()
+        .map(Vec::len)
+        .collect();
+    degrees.sort_unstable();
+    Ok(degrees)
+}
+
+/// Returns (M, p^e) with n = M*p^e and p not dividing M. Returns an error if `p` isn't prime or `n` is 0.
+fn split_multiplicity(n: usize, p: i32) -> Result<(usize, usize), NtruError> {
+    if !is_prime(p) {
+        return Err(NtruError::InvalidParameters("modulus `p` must be prime"));
+    }
+    if n == 0 {
+        return Err(NtruError::InvalidParameters("N must be positive"));
+    }
+
+    let (mut m, mut multiplicity) = (n, 1);
+    while m.is_multiple_of(p as usize) {
+        m /= p as usize;
+        multiplicity *= p as usize;
+    }
+    Ok((m, multiplicity))
+}
+
+/// The factorization of x^N - 1 into monic irreducible polynomials over F_p, for a prime p
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicFactorization {
+    /// The degree N of x^N - 1
+    n: usize,
+    /// The prime the factorization is over
+    p: i32,
+    /// The number of times each factor divides x^N - 1
+    multiplicity: usize,
+    /// The distinct factors, ordered by degree and then by coefficients, each with coefficients in \[0, p) from the
+    /// constant term up
+    factors: Vec<Vec<i64>>,
+    /// Each factor raised to the multiplicity
+    moduli: Vec<Vec<i64>>,
+    /// For each factor, the polynomial that is 1 modulo its power and 0 modulo the powers of every other factor
+    idempotents: Vec<Vec<i64>>,
+}
+
+impl CyclicFactorization {
+    /// Factors x^n - 1 over F_p. Returns an error if `p` isn't prime or `n` is 0.
+    pub fn new(n: usize, p: i32) -> Result<CyclicFactorization, NtruError> {
+        let (m, multiplicity) = split_multiplicity(n, p)?;
+        let mut rng = rand::thread_rng();
+        let p_wide = p as i64;
+
+        // x^M - 1 is the product of the cyclotomic polynomials Φ_d for d | M, whose factors all have degree ord_d(p)
+        let mut factors = Vec::new();
+        for (d, cyclotomic) in (1..=m)
+            .filter(|&d| m.is_multiple_of(d))
+            .zip(ReductionPoly::cyclotomic_factors(m))
+        {
+            let degree = multiplicative_order(p, d).unwrap_or(1);
+            let cyclotomic = reduce(&cyclotomic.coeffs(), p_wide);
+            factors.extend(equal_degree_factors(
+                cyclotomic, degree, m, p_wide, &mut rng,
+            ));
+        }
+        factors.sort_by(|a, b| {
+            a.len()
+                .cmp(&b.len())
+                .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+        });
+
+        // Each idempotent is c(x)*(c(x)^-1 mod φ(x)^e) for the cofactor c(x) = (x^n - 1)/φ(x)^e
+        let mut cyclic = vec![0; n + 1];
+        (cyclic[0], cyclic[n]) = (p_wide - 1, 1);
+        let moduli: Vec<Vec<i64>> = factors
+            .iter()
+            .map(|factor| {
+                (1..multiplicity).fold(factor.clone(), |acc, _| mul(&acc, factor, p_wide))
+            })
+            .collect();
+        let idempotents = moduli
+            .iter()
+            .map(|modulus| {
+                let (cofactor, _) = div_rem_mod_prime(cyclic.clone(), modulus, p_wide);
+                // The factors are distinct and irreducible, so the cofactor is coprime to the modulus
+                let inverse = inverse_mod(&cofactor, modulus, p_wide).unwrap_or_default();
+                mul(&cofactor, &inverse, p_wide)
+            })
+            .collect();
+
+        Ok(CyclicFactorization {
+            n,
+            p,
+            multiplicity,
+            factors,
+            moduli,
+            idempotents,
+        })
+    }
+
+    /// Returns the degree N of x^N - 1
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the prime the factorization is over
+    pub fn p(&self) -> i32 {
+        self.p
+    }
+
+    /// Returns the number of times each factor divides x^N - 1, which is the largest power of p dividing N
+    pub fn multiplicity(&self) -> usize {
+        self.multiplicity
+    }
+
+    /// Returns the distinct monic irreducible factors, ordered by degree, with coefficients in \[0, p)
+    pub fn factors(&self) -> Vec<ConvPoly> {
+        self.factors
+            .iter()
+            .map(|factor| ConvPoly {
+                coeffs: factor.iter().map(|&c| c as i32).collect(),
+            })
+            .collect()
+    }
+
+    /// Returns the degrees of the distinct factors in increasing order
+    pub fn degrees(&self) -> Vec<usize> {
+        self.factors.iter().map(|factor| factor.len() - 1).collect()
+    }
+
+    /// Computes the inverse of a polynomial within the ring (Z/pZ)\[x\]/(x^N - 1) by inverting it modulo the power of
+    /// each factor, and recombining the inverses by the Chinese remainder theorem. The result matches
+    /// [`ConvPoly::inverse`]. Returns an error if the polynomial shares a factor with x^N - 1.
+    pub fn inverse(&self, poly: &ConvPoly) -> Result<ConvPoly, NtruError> {
+        let p = self.p as i64;
+        let mut a = vec![0; self.n];
+        for (i, &coeff) in poly.coeffs.iter().enumerate() {
+            a[i % self.n] = (a[i % self.n] + coeff as i64).rem_euclid(p);
+        }
+
+        let mut result = vec![0; 2 * self.n];
+        for (modulus, idempotent) in self.moduli.iter().zip(&self.idempotents) {
+            let (_, component) = div_rem_mod_prime(a.clone(), modulus, p);
+            let inverse = inverse_mod(&component, modulus, p).ok_or(NtruError::NotInvertible)?;
+            for (i, c) in mul(&inverse, idempotent, p).into_iter().enumerate() {
+                result[i] += c;
+            }
+        }
+
+        let coeffs = (0..self.n)
+            .map(|i| ((result[i] + result[i + self.n]) % p) as i32)
+            .collect();
+        Ok(ConvPoly { coeffs }.trim())
+    }
+}
+
+/// Display implementation for factorizations, in the form "x^N - 1 = (f1)(f2)...(fk) over F_p", with each factor
+/// raised to the multiplicity if it's greater than 1
+impl fmt::Display for CyclicFactorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x^{} - 1 = ", self.n)?;
+        for factor in self.factors() {
+            write!(f, "({})", factor)?;
+            if self.multiplicity > 1 {
+                write!(f, "^{}", self.multiplicity)?;
+            }
+        }
+        write!(f, " over F_{}", self.p)
+    }
+}
+
+/// Splits a monic squarefree divisor g(x) of x^m - 1 over F_p, whose irreducible factors all have the given degree,
+/// into those factors
+fn equal_degree_factors(
+    g: Vec<i64>,
+    degree: usize,
+    m: usize,
+    p: i64,
+    rng: &mut impl Rng,
+) -> Vec<Vec<i64>> {
+    let mut pending = vec![g];
+    let mut factors = Vec::new();
+    while let Some(g) = pending.pop() {
+        if g.len() - 1 == degree {
+            factors.push(g);
+            continue;
+        }
+
+        loop {
+            // Modulo each factor, the trace of a random h(x) is a uniformly random element of F_p. For odd p,
+            // t^((p - 1)/2) is then 1 for half of the non-zero traces t and -1 for the other half.
+            let h: Vec<i64> = (0..g.len() - 1).map(|_| rng.gen_range(0..p)).collect();
+            let mut trace = vec![0; m];
+            let mut power = 1;
+            for _ in 0..degree {
+                // h(x)^(p^i) = h(x^(p^i)), and x^(p^i) = x^(p^i mod m) modulo x^m - 1
+                for (j, &c) in h.iter().enumerate() {
+                    trace[j * power % m] += c;
+                }
+                power = power * p as usize % m;
+            }
+            let (_, trace) = div_rem_mod_prime(reduce_wide(&trace, p), &g, p);
+            let split = if p == 2 {
+                trace
+            } else {
+                let mut t = pow_mod_poly(&trace, (p as u64 - 1) / 2, &g, p);
+                match t.first_mut() {
+                    Some(c) => *c = (*c + p - 1) % p,
+                    None => t.push(p - 1),
+                }
+                t
+            };
+
+            let divisor = gcd_poly(split, g.clone(), p);
+            if divisor.len() > 1 && divisor.len() < g.len() {
+                let (quotient, _) = div_rem_mod_prime(g, &divisor, p);
+                pending.push(divisor);
+                pending.push(quotient);
+                break;
+            }
+        }
+    }
+    factors
+}
+
+/// Reduces coefficients modulo p
+fn reduce(coeffs: &[i32], p: i64) -> Vec<i64> {
+    coeffs.iter().map(|&c| (c as i64).rem_euclid(p)).collect()
+}
+
+/// Reduces coefficients modulo p
+fn reduce_wide(coeffs: &[i64], p: i64) -> Vec<i64> {
+    coeffs.iter().map(|&c| c.rem_euclid(p)).collect()
+}
+
+/// Returns the product of two polynomials over F_p, given by their coefficients reduced modulo p
+fn mul(a: &[i64], b: &[i64], p: i64) -> Vec<i64> {
+    let a: Vec<i32> = a.iter().map(|&c| c as i32).collect();
+    let b: Vec<i32> = b.iter().map(|&c| c as i32).collect();
+    polynomial_product(&a, &b, MulBackend::Auto)
+        .into_iter()
+        .map(|c| c.rem_euclid(p as i128) as i64)
+        .collect()
+}
+
+/// Returns base(x)^exp modulo g(x) over F_p
+fn pow_mod_poly(base: &[i64], mut exp: u64, g: &[i64], p: i64) -> Vec<i64> {
+    let mut result = vec![1];
+    let mut base = base.to_vec();
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = div_rem_mod_prime(mul(&result, &base, p), g, p).1;
+        }
+        base = div_rem_mod_prime(mul(&base, &base, p), g, p).1;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns the monic gcd of two polynomials over F_p, the second of which must be non-zero
+fn gcd_poly(a: Vec<i64>, b: Vec<i64>, p: i64) -> Vec<i64> {
+    let (_, mut r) = div_rem_mod_prime(a, &b, p);
+    let mut old_r = b;
+    while !r.is_empty() {
+        let (_, new_r) = div_rem_mod_prime(old_r, &r, p);
+        (old_r, r) = (r, new_r);
+    }
+    let lc_inv = pow_mod(old_r[old_r.len() - 1] as u64, p as u64 - 2, p as u64) as i64;
+    old_r.iter().map(|&c| c * lc_inv % p).collect()
+}
+
+/// Returns the inverse of a(x) modulo g(x) over F_p using the Extended Euclidean Algorithm, or None if they aren't
+/// coprime
+fn inverse_mod(a: &[i64], g: &[i64], p: i64) -> Option<Vec<i64>> {
+    let (mut old_r, mut r) = (g.to_vec(), div_rem_mod_prime(a.to_vec(), g, p).1);
+    let (mut old_s, mut s) = (vec![], vec![1]);
+    while !r.is_empty() {
+        let (q, new_r) = div_rem_mod_prime(old_r, &r, p);
+        (old_r, r) = (r, new_r);
+        let qs = mul(&q, &s, p);
+        let new_s = (0..old_s.len().max(qs.len()))
+            .map(|i| {
+                let x = old_s.get(i).copied().unwrap_or(0) - qs.get(i).copied().unwrap_or(0);
+                x.rem_euclid(p)
+            })
+            .collect();
+        (old_s, s) = (s, new_s);
+    }
+
+    // The gcd must be a non-zero constant
+    if old_r.len() != 1 {
+        return None;
+    }
+    let c_inv = pow_mod(old_r[0] as u64, p as u64 - 2, p as u64) as i64;
+    let scaled = old_s.iter().map(|&c| c * c_inv % p).collect();
+    Some(div_rem_mod_prime(scaled, g, p).1)
+}
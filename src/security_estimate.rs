@@ -0,0 +1,213 @@
+use crate::error::NtruError;
+use crate::params::NtruParams;
+use std::f64::consts::{E, PI};
+use std::fmt;
+
+// An NTRU public key h(x) ≡ Fq(x)*g(x) (mod q) defines the NTRU lattice L_h = {(a, b) : b(x) ≡ a(x)*h(x) (mod q)} of
+// dimension 2N and determinant q^N, which contains the unusually short vector (f, g). Both attacks estimated here
+// recover (f, g) by lattice reduction; their cost is measured with the core-SVP methodology, which charges a BKZ
+// reduction with block size β the cost of a single SVP call in dimension β.
+
+/// The classical core-SVP cost exponent: one SVP call in dimension β costs 2^(0.292β) operations (sieving)
+const CLASSICAL_SIEVING_EXPONENT: f64 = 0.292;
+/// The quantum core-SVP cost exponent: one SVP call in dimension β costs 2^(0.265β) operations (quantum sieving)
+const QUANTUM_SIEVING_EXPONENT: f64 = 0.265;
+/// The smallest block size the BKZ cost model is meaningful for
+const MIN_BLOCK_SIZE: usize = 50;
+
+/// The estimated cost of the primal attack, which runs BKZ on (a sublattice of) L_h until (f, g) is the shortest
+/// vector of the final projected block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrimalAttack {
+    /// The dimension of the sublattice of L_h the attack reduces
+    pub dimension: usize,
+    /// The smallest BKZ block size that recovers (f, g)
+    pub block_size: usize,
+    /// log2 of the classical core-SVP cost
+    pub classical_bits: f64,
+    /// log2 of the quantum core-SVP cost
+    pub quantum_bits: f64,
+}
+
+/// The estimated cost of the hybrid attack, which guesses part of f(x) with a meet-in-the-middle search and
+/// recovers the rest by reducing the remaining lattice
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HybridAttack {
+    /// The number of coefficients of f(x) that are guessed
+    pub guessed_coeffs: usize,
+    /// The BKZ block size used to reduce the remaining lattice of dimension 2N - guessed_coeffs
+    pub block_size: usize,
+    /// log2 of the classical core-SVP cost of the lattice reduction
+    pub reduction_bits: f64,
+    /// log2 of the classical cost of the meet-in-the-middle search over the guessed coefficients
+    pub meet_in_the_middle_bits: f64,
+    /// log2 of the overall classical cost, the larger of the two phases
+    pub classical_bits: f64,
+}
+
+/// An estimate of the security of a parameter set against lattice attacks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityEstimate {
+    /// The parameter set the estimate is about
+    pub params: NtruParams,
+    /// The dimension 2N of the NTRU lattice
+    pub lattice_dimension: usize,
+    /// The cost of the primal attack
+    pub primal: PrimalAttack,
+    /// The cost of the hybrid attack
+    pub hybrid: HybridAttack,
+    /// The estimated classical security in bits, i.e. the cost of the cheapest attack
+    pub classical_bits: f64,
+    /// The estimated quantum security in bits. The hybrid attack's meet-in-the-middle phase has no well-established
+    /// quantum speedup, so the hybrid attack is charged its classical cost.
+    pub quantum_bits: f64,
+}
+
+/// Display implementation for security estimates. The estimate is summarized over several lines, ending with the
+/// estimated security in bits.
+impl fmt::Display for SecurityEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let NtruParams { n, p, q, d } = self.params;
+        writeln!(
+            f,
+            "NTRU parameters N = {}, p = {}, q = {}, d = {}",
+            n, p, q, d
+        )?;
+        writeln!(f, "Lattice dimension: {}", self.lattice_dimension)?;
+        writeln!(
+            f,
+            "Primal attack: dimension {}, BKZ-{}, 2^{:.1} classical, 2^{:.1} quantum",
+            self.primal.dimension,
+            self.primal.block_size,
+            self.primal.classical_bits,
+            self.primal.quantum_bits
+        )?;
+        writeln!(
+            f,
+            "Hybrid attack: {} guessed coefficients, BKZ-{} (2^{:.1}), meet-in-the-middle 2^{:.1}",
+            self.hybrid.guessed_coeffs,
+            self.hybrid.block_size,
+            self.hybrid.reduction_bits,
+            self.hybrid.meet_in_the_middle_bits
+        )?;
+        writeln!(
+            f,
+            "Estimated security: {:.1} bits classical, {:.1} bits quantum",
+            self.classical_bits, self.quantum_bits
+        )
+    }
+}
+
+/// Estimates the security of the given parameter set against the primal and hybrid lattice attacks on the ternary
+/// private key f(x) in T(d + 1, d) and g(x) in T(d, d). Returns an error if the parameter set is unusable or its
+/// lattice is too small for the BKZ cost model to apply.
+pub fn estimate_security(params: &NtruParams) -> Result<SecurityEstimate, NtruError> {
+    params.ensure_usable()?;
+    let out_of_model =
+        NtruError::InvalidParameters("the NTRU lattice is too small for the BKZ cost model");
+    let primal = primal_attack(params).ok_or(out_of_model.clone())?;
+    let hybrid = hybrid_attack(params).ok_or(out_of_model)?;
+
+    Ok(SecurityEstimate {
+        params: *params,
+        lattice_dimension: 2 * params.n,
+        primal,
+        hybrid,
+        classical_bits: primal.classical_bits.min(hybrid.classical_bits),
+        quantum_bits: primal.quantum_bits.min(hybrid.classical_bits),
+    })
+}
+
+/// Estimates the primal attack, optimizing over the dimension of the sublattice that is reduced. Keeping only m of
+/// the N coordinates of g(x) projects L_h onto a lattice of dimension N + m and determinant q^m, which still contains
+/// the projection of (f, g). Returns None if no block size the cost model covers recovers the secret.
+fn primal_attack(params: &NtruParams) -> Option<PrimalAttack> {
+    let n = params.n;
+    let sigma = secret_stddev(params);
+
+    (1..=n)
+        .filter_map(|m| {
+            min_block_size(n + m, m, params.q, sigma).map(|block_size| (n + m, block_size))
+        })
+        .min_by_key(|&(_, block_size)| block_size)
+        .map(|(dimension, block_size)| PrimalAttack {
+            dimension,
+            block_size,
+            classical_bits: CLASSICAL_SIEVING_EXPONENT * block_size as f64,
+            quantum_bits: QUANTUM_SIEVING_EXPONENT * block_size as f64,
+        })
+}
+
+/// Estimates the hybrid attack, optimizing over the number of guessed coefficients of f(x). The guessed
+/// coefficients are expected to contain their share of the 1s and -1s of f(x), and a meet-in-the-middle search
+/// finds them in roughly the square root of the number of candidates. Returns None if no block size the cost model
+/// covers recovers the secret.
+fn hybrid_attack(params: &NtruParams) -> Option<HybridAttack> {
+    let NtruParams { n, q, d, .. } = *params;
+    let sigma = secret_stddev(params);
+    let log2_factorials = log2_factorials(n);
+
+    (0..n)
+        .filter_map(|guessed| {
+            // Subtracting the contribution of the guessed coefficients leaves a lattice of dimension 2N - guessed and
+            // determinant q^N
+            let block_size = min_block_size(2 * n - guessed, n, q, sigma)?;
+            let reduction_bits = CLASSICAL_SIEVING_EXPONENT * block_size as f64;
+
+            // Expected number of 1s and -1s among the guessed coefficients
+            let ones = (guessed * (d + 1) + n / 2) / n;
+            let neg_ones = (guessed * d + n / 2) / n;
+            let log2_candidates = log2_factorials[guessed]
+                - log2_factorials[ones]
+                - log2_factorials[neg_ones]
+                - log2_factorials[guessed - ones - neg_ones];
+            let meet_in_the_middle_bits = log2_candidates / 2.0;
+
+            Some(HybridAttack {
+                guessed_coeffs: guessed,
+                block_size,
+                reduction_bits,
+                meet_in_the_middle_bits,
+                classical_bits: reduction_bits.max(meet_in_the_middle_bits),
+            })
+        })
+        .min_by(|a, b| a.classical_bits.total_cmp(&b.classical_bits))
+}
+
+/// Returns the standard deviation of a single coefficient of the secret vector (f, g), where f(x) is in T(d + 1, d)
+/// and g(x) in T(d, d)
+fn secret_stddev(params: &NtruParams) -> f64 {
+    let squared_norm = (4 * params.d + 1) as f64;
+    (squared_norm / (2 * params.n) as f64).sqrt()
+}
+
+/// Returns the smallest BKZ block size β that recovers a secret with per-coefficient standard deviation `sigma`
+/// from a lattice of the given dimension and determinant q^k, or None if no block size up to the dimension does.
+/// The secret is found once its projection onto the last β Gram-Schmidt vectors is shorter than the β-th last
+/// Gram-Schmidt norm, i.e. once σ*sqrt(β) <= δ_β^(2β - dimension - 1) * q^(k / dimension).
+fn min_block_size(dimension: usize, k: usize, q: i32, sigma: f64) -> Option<usize> {
+    let log2_volume = k as f64 * (q as f64).log2() / dimension as f64;
+
+    (MIN_BLOCK_SIZE..=dimension).find(|&beta| {
+        let lhs = (sigma * (beta as f64).sqrt()).log2();
+        let rhs = (2.0 * beta as f64 - dimension as f64 - 1.0) * root_hermite_factor(beta).log2()
+            + log2_volume;
+        lhs <= rhs
+    })
+}
+
+/// Returns the root Hermite factor δ_β = ((πβ)^(1/β) * β / (2πe))^(1 / (2(β - 1))) achieved by BKZ with block size β
+fn root_hermite_factor(beta: usize) -> f64 {
+    let beta = beta as f64;
+    ((PI * beta).powf(1.0 / beta) * beta / (2.0 * PI * E)).powf(1.0 / (2.0 * (beta - 1.0)))
+}
+
+/// Returns log2(k!) for every k in [0, n]
+fn log2_factorials(n: usize) -> Vec<f64> {
+    let mut result = Vec::with_capacity(n + 1);
+    result.push(0.0);
+    for k in 1..=n {
+        result.push(result[k - 1] + (k as f64).log2());
+    }
+    result
+}
@@ -0,0 +1,172 @@
+use crate::constant_time::{expand_ternary, random_positions};
+use crate::convolution_polynomial::ConvPoly;
+use crate::error::NtruError;
+use crate::poly_ring::{PolyRing, ReductionPoly, RingElement};
+use std::ops::Mul;
+
+// The private key f(x), g(x) and the perturbation r(x) only have 2d or 2d + 1 non-zero coefficients out of N, all of
+// them 1 or -1. Storing the positions of those coefficients lets a product with a dense polynomial be computed as a
+// sum of 2d + 1 rotations of it: O(N*d) additions and no multiplications at all.
+
+/// A ternary polynomial in Z\[x\]/(x^N - 1), stored as the positions of its 1 and -1 coefficients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseTernaryPoly {
+    /// The number of coefficients N
+    n: usize,
+    /// The powers of x whose coefficient is 1, in increasing order
+    ones: Vec<usize>,
+    /// The powers of x whose coefficient is -1, in increasing order
+    neg_ones: Vec<usize>,
+}
+
+impl SparseTernaryPoly {
+    /// Generates a random ternary polynomial with N coefficients, `num_ones` of them 1 and `num_neg_ones` of them
+    /// -1, in a time that depends only on N and the counts. Returns an error if `n` is 0 or there are more 1s and -1s
+    /// than coefficients.
+    pub fn random(
+        n: usize,
+        num_ones: usize,
+        num_neg_ones: usize,
+    ) -> Result<SparseTernaryPoly, NtruError> {
+        if n == 0 {
+            return Err(NtruError::InvalidParameters(
+                "polynomial degree should be greater than 0",
+            ));
+        }
+        let (ones, neg_ones) = random_positions(n, num_ones, num_neg_ones)?;
+        Ok(SparseTernaryPoly { n, ones, neg_ones })
+    }
+
+    /// Converts a polynomial with at most N coefficients, all of them -1, 0 or 1, into its sparse form. Returns an
+    /// error if it has more than N coefficients or any coefficient isn't ternary.
+    pub fn from_poly(poly: &ConvPoly, n: usize) -> Result<SparseTernaryPoly, NtruError> {
+        if poly.coeffs.len() > n {
+            return Err(NtruError::ParameterMismatch(
+                "polynomial has more than N coefficients",
+            ));
+        }
+
+        let (mut ones, mut neg_ones) = (Vec::new(), Vec::new());
+        for (i, &coeff) in poly.coeffs.iter().enumerate() {
+            match coeff {
+                1 => ones.push(i),
+                -1 => neg_ones.push(i),
+                0 => {}
+                _ => {
+                    return Err(NtruError::InvalidEncoding(
+                        "polynomial coefficients must be ternary",
+                    ))
+                }
+            }
+        }
+
+        Ok(SparseTernaryPoly { n, ones, neg_ones })
+    }
+
+    /// Returns the number of coefficients N
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the powers of x whose coefficient is 1, in increasing order
+    pub fn ones(&self) -> &[usize] {
+        &self.ones
+    }
+
+    /// Returns the powers of x whose coefficient is -1, in increasing order
+    pub fn neg_ones(&self) -> &[usize] {
+        &self.neg_ones
+    }
+
+    /// Returns the polynomial in its dense form
+    pub fn to_poly(&self) -> ConvPoly {
+        let mut coeffs = vec![0; self.n];
+        for &i in &self.ones {
+            coeffs[i] = 1;
+        }
+        for &i in &self.neg_ones {
+            coeffs[i] = -1;
+        }
+        ConvPoly { coeffs }.trim()
+    }
+
+    /// Maps the polynomial into the given ring without branching on or accessing memory at its positions, so that it
+    /// can be used on secret keys. Returns an error if the ring doesn't have N coefficients.
+    pub fn to_element_constant_time(&self, ring: &PolyRing) -> Result<RingElement, NtruError> {
+        if ring.n() != self.n {
+            return Err(NtruError::RingMismatch);
+        }
+        ring.element_constant_time(&expand_ternary(self.n, &self.ones, &self.neg_ones))
+    }
+
+    /// Returns the product of this polynomial with a ring element. Returns an error if the element's ring doesn't
+    /// have N coefficients.
+    pub fn try_mul(&self, other: &RingElement) -> Result<RingElement, NtruError> {
+        let ring = other.ring();
+        if ring.n() != self.n {
+            return Err(NtruError::RingMismatch);
+        }
+        // Only x^N - 1 and x^N + 1 turn multiplication by x^i into a rotation
+        let negacyclic = match ring.reduction() {
+            ReductionPoly::Cyclic(_) => false,
+            ReductionPoly::Negacyclic(_) => true,
+            _ => return ring.element(&self.to_poly()).try_mul(other),
+        };
+
+        // x^i * b(x) rotates the coefficients of b(x) i places up, wrapping the top i around to the bottom (negated
+        // if x^N = -1). The rotations for the +1 coefficients are added up in plus and those for the -1 coefficients
+        // in minus, with a negated wrapped part going to the other sum, so that only additions are needed.
+        let b = other.coeffs();
+        let (mut plus, mut minus) = (vec![0i64; self.n], vec![0i64; self.n]);
+        for &i in &self.ones {
+            add_shifted(&mut plus, b, i);
+            add_wrapped(if negacyclic { &mut minus } else { &mut plus }, b, i);
+        }
+        for &i in &self.neg_ones {
+            add_shifted(&mut minus, b, i);
+            add_wrapped(if negacyclic { &mut plus } else { &mut minus }, b, i);
+        }
+
+        let m = ring.modulus() as i64;
+        Ok(ring.element(&ConvPoly {
+            coeffs: plus
+                .into_iter()
+                .zip(minus)
+                .map(|(add, sub)| (add - sub).rem_euclid(m) as i32)
+                .collect(),
+        }))
+    }
+}
+
+/// Multiplies a sparse ternary polynomial with a ring element, panicking if the element's ring doesn't have N
+/// coefficients
+impl Mul<&RingElement> for &SparseTernaryPoly {
+    type Output = RingElement;
+
+    fn mul(self, rhs: &RingElement) -> RingElement {
+        self.try_mul(rhs)
+            .unwrap_or_else(|err| panic!("{}: N = {} and {}", err, self.n, rhs.ring()))
+    }
+}
+
+impl Mul<&SparseTernaryPoly> for &RingElement {
+    type Output = RingElement;
+
+    fn mul(self, rhs: &SparseTernaryPoly) -> RingElement {
+        rhs * self
+    }
+}
+
+/// Adds the coefficients of b(x) to those of acc from x^i up, dropping the ones that would pass x^(N-1)
+fn add_shifted(acc: &mut [i64], b: &[i32], i: usize) {
+    for (sum, &coeff) in acc[i..].iter_mut().zip(b) {
+        *sum += coeff as i64;
+    }
+}
+
+/// Adds the top i coefficients of b(x) to the bottom i coefficients of acc
+fn add_wrapped(acc: &mut [i64], b: &[i32], i: usize) {
+    for (sum, &coeff) in acc[..i].iter_mut().zip(&b[b.len() - i..]) {
+        *sum += coeff as i64;
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod coeff_stats_tests {
+    use ntru_rs::convolution_polynomial::ternary_polynomial;
+    use ntru_rs::{CoeffStats, ConvPoly, NtruError, NtruParams};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_coeff_stats() {
+        // 1 + 7x + 5x^2 + 3x^4 (mod 8) center-lifts to 1 - x - 3x^2 + 3x^4, with x^3 and x^5 zero
+        let poly = ConvPoly {
+            coeffs: vec![1, 7, 5, 0, 3],
+        };
+        let stats = CoeffStats::new(&poly, 6, 8).unwrap();
+        assert_eq!(stats.centered.coeffs, vec![1, -1, -3, 0, 3]);
+        assert_eq!(
+            stats.histogram,
+            BTreeMap::from([(-3, 1), (-1, 1), (0, 2), (1, 1), (3, 1)])
+        );
+        assert_eq!((stats.ones(), stats.neg_ones(), stats.zeros()), (1, 1, 2));
+        assert_eq!(stats.weight(), 4);
+        assert_eq!(stats.count(4), 0);
+        assert_eq!(stats.l1_norm(), 8);
+        assert_eq!(stats.l2_norm(), 20f64.sqrt());
+        assert_eq!(stats.linf_norm(), 3);
+        assert!(!stats.is_ternary(1, 1));
+
+        // Coefficients past x^(n - 1) are folded in first: 2 + x^3 = 3 in Z[x]/(x^3 - 1)
+        let stats = CoeffStats::new(
+            &ConvPoly {
+                coeffs: vec![2, 0, 0, 1],
+            },
+            3,
+            5,
+        )
+        .unwrap();
+        assert_eq!(stats.centered, ConvPoly::constant(-2));
+
+        let poly = ConvPoly::constant(1);
+        assert!(matches!(
+            CoeffStats::new(&poly, 0, 3),
+            Err(NtruError::InvalidParameters(_))
+        ));
+        assert_eq!(
+            CoeffStats::new(&poly, 3, 0),
+            Err(NtruError::InvalidModulus(0))
+        );
+    }
+
+    #[test]
+    fn test_ternary_keys_survive_reduction() {
+        // A key in T(d + 1, d) stored modulo q is still recognized as one
+        let NtruParams { n, q, d, .. } = NtruParams::default();
+        let f = ternary_polynomial(n, d + 1, d).unwrap();
+        let stats = CoeffStats::new(&f.modulo(q).unwrap(), n, q).unwrap();
+        assert!(stats.is_ternary(d + 1, d));
+        assert!(!stats.is_ternary(d, d + 1));
+        assert_eq!(stats.centered, f);
+        assert_eq!(stats.weight(), 2 * d + 1);
+        let expected = ((2 * d + 1) as f64 - 1.0 / n as f64).sqrt();
+        assert!((stats.centered_norm() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_decryption_noise() {
+        // a(x) ≡ e(x)*f(x) (mod q) center-lifts to p*r(x)*g(x) + f(x)*m(x), which lies inside (-q/2, q/2]
+        let NtruParams { n, p, q, d } = NtruParams::default();
+        let g = ternary_polynomial(n, d, d).unwrap();
+        let f = loop {
+            let f = ternary_polynomial(n, d + 1, d).unwrap();
+            if f.is_invertible(q, n) {
+                break f;
+            }
+        };
+        let h = f.inverse(q, n).unwrap().mul_mod(&g, n, q).unwrap();
+        let r = ternary_polynomial(n, d, d).unwrap();
+        let m = ternary_polynomial(n, n / 3, n / 3).unwrap();
+        let e = ConvPoly::constant(p)
+            .mul(&r.mul(&h, n), n)
+            .add(&m)
+            .modulo(q)
+            .unwrap();
+        let a = e.mul_mod(&f, n, q).unwrap();
+
+        let stats = CoeffStats::new(&a, n, q).unwrap();
+        let noise = ConvPoly::constant(p)
+            .mul(&r.mul(&g, n), n)
+            .add(&f.mul(&m, n));
+        assert_eq!(stats.centered, noise);
+        // Each coefficient of r(x)*g(x) is at most 2d and each of f(x)*m(x) at most 2d + 1 in absolute value
+        assert!(noise.linf_norm() as usize <= 2 * d * p as usize + 2 * d + 1);
+        assert!(stats.linf_norm() <= q as u32 / 2);
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod compact_poly_tests {
+    use ntru_rs::{ConvPoly, NtruError, PolyModP, PolyModQ, PolyRing};
+    use rand::Rng;
+
+    #[test]
+    fn test_mod_q_round_trip() {
+        let mut rng = rand::thread_rng();
+        for q in [383, 2048, 1 << 16] {
+            let ring = PolyRing::new(509, q).unwrap();
+            let element = ring.element(&ConvPoly {
+                coeffs: (0..509).map(|_| rng.gen_range(0..q)).collect(),
+            });
+            let compact = PolyModQ::from_element(&element).unwrap();
+            assert_eq!(compact.n(), 509);
+            assert_eq!(compact.to_element(&ring).unwrap(), element);
+            assert_eq!(compact.to_poly(), element.to_poly());
+
+            let decoded = PolyModQ::from_be_bytes(&compact.to_be_bytes()).unwrap();
+            assert_eq!(decoded, compact);
+        }
+    }
+
+    #[test]
+    fn test_mod_q_invalid() {
+        let large = PolyRing::new(5, (1 << 16) + 1).unwrap();
+        assert_eq!(
+            PolyModQ::from_element(&large.constant(1)),
+            Err(NtruError::InvalidModulus((1 << 16) + 1))
+        );
+
+        let ring = PolyRing::new(3, 11).unwrap();
+        assert!(matches!(
+            PolyModQ::new(vec![1, 2]).to_element(&ring),
+            Err(NtruError::ParameterMismatch(_))
+        ));
+        assert!(matches!(
+            PolyModQ::new(vec![1, 2, 11]).to_element(&ring),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            PolyModQ::from_be_bytes(&[0, 1, 2]),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_mod_p_round_trip() {
+        // For p = 3 the stored coefficients are ternary
+        let ring = PolyRing::new(7, 3).unwrap();
+        let element = ring.element(&ConvPoly {
+            coeffs: vec![0, 1, 2, 0, 2, 1],
+        });
+        let compact = PolyModP::from_element(&element).unwrap();
+        assert_eq!(compact.coeffs(), &[0, 1, -1, 0, -1, 1, 0]);
+        assert_eq!(compact.to_element(&ring).unwrap(), element);
+        assert_eq!(compact.to_poly(), element.center_lift());
+
+        // Center-lifted coefficients of the largest supported modulus still fit in an i8
+        let ring = PolyRing::new(3, 255).unwrap();
+        let element = ring.element(&ConvPoly {
+            coeffs: vec![127, 128, 254],
+        });
+        let compact = PolyModP::from_element(&element).unwrap();
+        assert_eq!(compact.coeffs(), &[127, -127, -1]);
+        assert_eq!(compact.to_element(&ring).unwrap(), element);
+
+        let large = PolyRing::new(3, 256).unwrap();
+        assert_eq!(
+            PolyModP::from_element(&large.constant(1)),
+            Err(NtruError::InvalidModulus(256))
+        );
+    }
+}
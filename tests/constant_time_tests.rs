@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod constant_time_tests {
+    use ntru_rs::constant_time::{center_lift, expand_ternary, modulo, product};
+    use ntru_rs::convolution_polynomial::{self, ternary_polynomial};
+    use ntru_rs::{ConvPoly, NtruError, PolyRing, ReductionPoly, SparseTernaryPoly};
+    use rand::Rng;
+
+    const MODULI: [i32; 8] = [1, 2, 3, 7, 383, 2048, 65537, i32::MAX];
+
+    #[test]
+    fn test_modulo_and_center_lift() {
+        let mut rng = rand::thread_rng();
+        let mut values: Vec<i64> = vec![0, 1, -1, i64::MAX, i64::MIN, i64::MIN + 1];
+        values.extend((0..200).map(|_| rng.gen::<i64>()));
+        values.extend((0..200).map(|_| rng.gen_range(-5000..5000)));
+        for m in MODULI {
+            let expected: Vec<i32> = values
+                .iter()
+                .map(|&c| c.rem_euclid(m as i64) as i32)
+                .collect();
+            assert_eq!(modulo(&values, m).unwrap(), expected, "modulo {}", m);
+
+            let lifted: Vec<i32> = expected
+                .iter()
+                .map(|&c| convolution_polynomial::center_lift(c, m).unwrap())
+                .collect();
+            assert_eq!(center_lift(&values, m).unwrap(), lifted, "modulo {}", m);
+        }
+
+        // Coefficients are kept, zeros and all
+        assert_eq!(modulo(&[3, 0, 6, 0i32], 3), Ok(vec![0, 0, 0, 0]));
+        assert_eq!(center_lift(&[2u16, 0, 1], 3), Ok(vec![-1, 0, 1]));
+        assert_eq!(modulo(&[1i8], 0), Err(NtruError::InvalidModulus(0)));
+        assert_eq!(center_lift(&[1i8], -3), Err(NtruError::InvalidModulus(-3)));
+    }
+
+    #[test]
+    fn test_product_matches_ring_multiplication() {
+        let mut rng = rand::thread_rng();
+        for n in [1, 2, 5, 16, 61] {
+            for reduction in [
+                ReductionPoly::Cyclic(n),
+                ReductionPoly::Negacyclic(n),
+                ReductionPoly::Cyclotomic(n + 1),
+            ] {
+                for m in MODULI {
+                    let ring = PolyRing::with_reduction(reduction.clone(), m).unwrap();
+                    let a: Vec<i32> = (0..n).map(|_| rng.gen()).collect();
+                    let b: Vec<i32> = (0..n).map(|_| rng.gen_range(-1..=1)).collect();
+                    let expected = ring.element(&ConvPoly { coeffs: a.clone() })
+                        * ring.element(&ConvPoly { coeffs: b.clone() });
+                    assert_eq!(
+                        product(&a, &b, &reduction, m).unwrap(),
+                        expected.coeffs(),
+                        "{}",
+                        ring
+                    );
+                }
+            }
+        }
+
+        let monic = ReductionPoly::Monic(vec![3, 0, -2, 1].into());
+        let (a, b) = ([5, -1, 7], [0, 2, 9]);
+        let ring = PolyRing::with_reduction(monic.clone(), 11).unwrap();
+        let expected = ring.element(&ConvPoly { coeffs: a.to_vec() })
+            * ring.element(&ConvPoly { coeffs: b.to_vec() });
+        assert_eq!(product(&a, &b, &monic, 11).unwrap(), expected.coeffs());
+
+        let cyclic = ReductionPoly::Cyclic(3);
+        assert!(matches!(
+            product(&[1, 2], &[1, 2, 3], &cyclic, 7),
+            Err(NtruError::ParameterMismatch(_))
+        ));
+        assert_eq!(
+            product(&[1, 2, 3], &[1, 2, 3], &cyclic, 0),
+            Err(NtruError::InvalidModulus(0))
+        );
+        assert!(matches!(
+            product(&[1i32; 0], &[1i32; 0], &ReductionPoly::Cyclic(0), 7),
+            Err(NtruError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_expand_ternary() {
+        assert_eq!(expand_ternary(5, &[0, 3], &[4]), vec![1, 0, 0, 1, -1]);
+        // Positions past N are ignored
+        assert_eq!(expand_ternary(3, &[1, 7], &[]), vec![0, 1, 0]);
+
+        for _ in 0..20 {
+            let poly = SparseTernaryPoly::random(101, 20, 15).unwrap();
+            let mut expected = poly.to_poly().coeffs;
+            expected.resize(101, 0);
+            assert_eq!(expand_ternary(101, poly.ones(), poly.neg_ones()), expected);
+        }
+    }
+
+    #[test]
+    fn test_random_ternary() {
+        let n = 61;
+        let mut chosen = vec![0; n];
+        for _ in 0..200 {
+            let poly = SparseTernaryPoly::random(n, 10, 7).unwrap();
+            assert_eq!((poly.ones().len(), poly.neg_ones().len()), (10, 7));
+            // Positions are distinct, in increasing order and within N
+            let mut positions: Vec<usize> =
+                poly.ones().iter().chain(poly.neg_ones()).copied().collect();
+            assert!(poly.ones().windows(2).all(|w| w[0] < w[1]));
+            assert!(poly.neg_ones().windows(2).all(|w| w[0] < w[1]));
+            positions.sort_unstable();
+            positions.dedup();
+            assert_eq!(positions.len(), 17);
+            for i in positions {
+                chosen[i] += 1;
+            }
+        }
+        // Each position is chosen 200 * 17/61 ≈ 56 times on average
+        assert!(chosen.iter().all(|&count| count > 20), "{:?}", chosen);
+
+        assert_eq!(SparseTernaryPoly::random(1, 1, 0).unwrap().ones(), &[0]);
+        assert!(SparseTernaryPoly::random(0, 0, 0).is_err());
+        assert!(SparseTernaryPoly::random(5, 3, 3).is_err());
+    }
+
+    #[test]
+    fn test_ring_elements() {
+        let ring = PolyRing::new(11, 32).unwrap();
+        let poly = ternary_polynomial(11, 4, 3).unwrap();
+        let sparse = SparseTernaryPoly::from_poly(&poly, 11).unwrap();
+        let element = ring.element(&poly);
+        assert_eq!(sparse.to_element_constant_time(&ring).unwrap(), element);
+        let mut coeffs = poly.coeffs.clone();
+        coeffs.resize(11, 0);
+        assert_eq!(ring.element_constant_time(&coeffs).unwrap(), element);
+        assert_eq!(element.center_lift_constant_time(), coeffs);
+
+        let other = ring.ternary(3, 3).unwrap();
+        assert_eq!(
+            element.mul_constant_time(&other).unwrap(),
+            &element * &other
+        );
+        assert!(matches!(
+            ring.element_constant_time(&[1, 2, 3]),
+            Err(NtruError::ParameterMismatch(_))
+        ));
+        assert_eq!(
+            element.mul_constant_time(&PolyRing::new(11, 31).unwrap().zero()),
+            Err(NtruError::RingMismatch)
+        );
+        assert_eq!(
+            sparse.to_element_constant_time(&PolyRing::new(13, 32).unwrap()),
+            Err(NtruError::RingMismatch)
+        );
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
     use ntru_rs::convolution_polynomial::{
-        center_lift, extended_gcd, gcd, inverse, ternary_polynomial, ConvPoly,
+        center_lift, extended_gcd, gcd, inverse, is_prime, ternary_polynomial, ConvPoly,
     };
+    use ntru_rs::{CoeffStats, NtruError};
     use rand::Rng;
 
     mod ternary_polynomial_tests {
@@ -16,30 +17,35 @@ mod tests {
                 let n = rand::thread_rng().gen_range(1..=15);
                 let num_ones = rand::thread_rng().gen_range(0..=n);
                 let num_neg_ones = rand::thread_rng().gen_range(0..=(n - num_ones));
-                let poly = ternary_polynomial(n, num_ones, num_neg_ones);
+                let poly = ternary_polynomial(n, num_ones, num_neg_ones).unwrap();
 
                 assert!(
                     poly.coeffs.len() <= n,
                     "Number of coefficients should be <= n"
                 );
-                assert_eq!(
-                    poly.coeffs.iter().filter(|&&c| c == 1).count(),
+                let stats = CoeffStats::new(&poly, n, 3).unwrap();
+                assert!(
+                    stats.is_ternary(num_ones, num_neg_ones),
+                    "Should lie in T({}, {}). N: {}, Poly: {:?}",
                     num_ones,
-                    "Number of 1 coefficients should match. N: {}, Poly: {:?}",
+                    num_neg_ones,
                     n,
                     poly
                 );
                 assert_eq!(
-                    poly.coeffs.iter().filter(|&&c| c == -1).count(),
-                    num_neg_ones,
-                    "Number of -1 coefficients should match"
-                );
-                assert!(
-                    poly.coeffs.iter().all(|&c| c >= -1 && c <= 1),
+                    stats.centered, poly,
                     "Coefficients should be ternary (-1, 0, 1)"
                 );
             }
         }
+
+        #[test]
+        fn test_ternary_polynomial_too_heavy() {
+            assert!(matches!(
+                ternary_polynomial(5, 3, 3),
+                Err(NtruError::InvalidParameters(_))
+            ));
+        }
     }
 
     mod convolution_polynomial_tests {
@@ -191,7 +197,7 @@ mod tests {
             let expected_result = ConvPoly {
                 coeffs: vec![1, 2, 3, 4], // 4x^3 + 3x^2 + 2x + 1
             };
-            let result = poly.modulo(5);
+            let result = poly.modulo(5).unwrap();
             assert_eq!(expected_result.coeffs, result.coeffs, "Modulo 5 failed");
 
             // Modulo 5 with negative coefficients
@@ -201,7 +207,7 @@ mod tests {
             let expected_result = ConvPoly {
                 coeffs: vec![1, 3, 3, 1], // x^3 + 3x^2 + 3x + 1
             };
-            let result = poly.modulo(5);
+            let result = poly.modulo(5).unwrap();
             assert_eq!(expected_result.coeffs, result.coeffs, "Modulo 5 failed");
 
             // Modulo 5 with the result being zero
@@ -209,14 +215,21 @@ mod tests {
                 coeffs: vec![-5, 10, 15, 20, 1000], // 1000x^4 + 20x^3 + 15x^2 + 10x - 5
             };
             let expected_result = ConvPoly::constant(0);
-            let result = poly.modulo(5);
+            let result = poly.modulo(5).unwrap();
             assert_eq!(expected_result.coeffs, result.coeffs, "Modulo 5 failed");
 
             // Modulo 5 with all coefficients being zero
             let poly = ConvPoly { coeffs: vec![0; 5] };
             let expected_result = ConvPoly::constant(0);
-            let result = poly.modulo(5);
+            let result = poly.modulo(5).unwrap();
             assert_eq!(expected_result.coeffs, result.coeffs, "Modulo 5 failed");
+
+            // Modulo a non-positive integer
+            assert_eq!(
+                poly.modulo(0),
+                Err(NtruError::InvalidModulus(0)),
+                "Modulo 0 should be an error"
+            );
         }
 
         #[test]
@@ -228,7 +241,7 @@ mod tests {
             let expected_lift = ConvPoly {
                 coeffs: vec![-2, 3, 1, 2, -3], // −3x^4 + 2x^3 + x^2 + 3x - 2
             };
-            let lift = poly.center_lift(7);
+            let lift = poly.center_lift(7).unwrap();
             assert_eq!(
                 expected_lift.coeffs, lift.coeffs,
                 "Center lift modulo 7 failed"
@@ -241,7 +254,7 @@ mod tests {
             let expected_lift = ConvPoly {
                 coeffs: vec![3, 0, -2, 1, 3], // 3x^4 + x^3 - 2x^2 + 3
             };
-            let lift = poly.center_lift(7);
+            let lift = poly.center_lift(7).unwrap();
             assert_eq!(
                 expected_lift.coeffs, lift.coeffs,
                 "Center lift modulo 7 failed"
@@ -309,7 +322,7 @@ mod tests {
             let expected_sum = ConvPoly {
                 coeffs: vec![4, 4, 4], // 4x^2 + 4x + 4
             };
-            let sum = poly1.add(&poly2).modulo(5);
+            let sum = poly1.add(&poly2).modulo(5).unwrap();
             assert_eq!(expected_sum.coeffs, sum.coeffs, "Addition modulo 5 failed");
 
             // Modulo 5 addition with wraparound
@@ -322,7 +335,7 @@ mod tests {
             let expected_sum = ConvPoly {
                 coeffs: vec![1, 2, 2], // 2x^2 + 2x + 1
             };
-            let sum = poly1.add(&poly2).modulo(5);
+            let sum = poly1.add(&poly2).modulo(5).unwrap();
             assert_eq!(expected_sum.coeffs, sum.coeffs, "Addition modulo 5 failed");
 
             // Another modulo 5 addition with wraparound
@@ -333,7 +346,7 @@ mod tests {
             let expected_sum = ConvPoly {
                 coeffs: vec![0, 0, 2],
             };
-            let sum = poly1.add(&poly2).modulo(5);
+            let sum = poly1.add(&poly2).modulo(5).unwrap();
             assert_eq!(
                 expected_sum.coeffs, sum.coeffs,
                 "Partial add modulo 5 failed"
@@ -401,7 +414,7 @@ mod tests {
             let expected_diff = ConvPoly {
                 coeffs: vec![2, 1], // x + 2
             };
-            let diff = poly1.sub(&poly2).modulo(5);
+            let diff = poly1.sub(&poly2).modulo(5).unwrap();
             assert_eq!(
                 expected_diff.coeffs, diff.coeffs,
                 "Subtraction modulo 5 failed"
@@ -417,7 +430,7 @@ mod tests {
             let expected_diff = ConvPoly {
                 coeffs: vec![2, 2, 2], // 2x^2 + 2x + 2
             };
-            let diff = poly1.sub(&poly2).modulo(5);
+            let diff = poly1.sub(&poly2).modulo(5).unwrap();
             assert_eq!(
                 expected_diff.coeffs, diff.coeffs,
                 "Subtraction modulo 5 failed"
@@ -523,11 +536,68 @@ mod tests {
             let expected_product = ConvPoly {
                 coeffs: vec![9, 9, 4, 8, 5], // 5x^4 + 8x^3 + 4x^2 + 9x + 10
             };
-            let product = poly1.mul(&poly2, 5).modulo(11);
+            let product = poly1.mul(&poly2, 5).modulo(11).unwrap();
             assert_eq!(
                 expected_product.coeffs, product.coeffs,
                 "Multiplication modulo 11 failed"
             );
+            assert_eq!(expected_product, poly1.mul_mod(&poly2, 5, 11).unwrap());
+            assert!(poly1.mul_mod(&poly2, 5, 0).is_err());
+        }
+
+        #[test]
+        fn test_mul_mod_standard_sizes() {
+            // With q = 4096 and N = 821 the unreduced product has coefficients of up to 821 * 4095^2 > 2^31
+            let mut rng = rand::thread_rng();
+            let (n, q) = (821, 4096);
+            let a = ConvPoly {
+                coeffs: (0..n).map(|_| rng.gen_range(0..q)).collect(),
+            };
+            let b = ConvPoly {
+                coeffs: (0..n).map(|_| rng.gen_range(0..q)).collect(),
+            };
+
+            // Reduce after every step of a schoolbook product, which can't overflow an i64
+            let mut expected = vec![0i64; n];
+            for (i, &x) in a.coeffs.iter().enumerate() {
+                for (j, &y) in b.coeffs.iter().enumerate() {
+                    let k = (i + j) % n;
+                    expected[k] = (expected[k] + x as i64 * y as i64) % q as i64;
+                }
+            }
+            let expected = ConvPoly {
+                coeffs: expected.into_iter().map(|c| c as i32).collect(),
+            }
+            .trim();
+            assert_eq!(expected, a.mul_mod(&b, n, q).unwrap());
+            assert_eq!(a.checked_mul(&b, n), Err(NtruError::Overflow));
+        }
+
+        #[test]
+        fn test_checked_arithmetic() {
+            let small = ConvPoly {
+                coeffs: vec![1, -2, 3],
+            };
+            let large = ConvPoly {
+                coeffs: vec![i32::MAX, i32::MIN],
+            };
+
+            assert_eq!(small.checked_add(&small).unwrap(), small.add(&small));
+            assert_eq!(small.checked_sub(&large).unwrap(), small.sub(&large));
+            assert_eq!(small.checked_mul(&small, 3).unwrap(), small.mul(&small, 3));
+
+            assert_eq!(large.checked_add(&large), Err(NtruError::Overflow));
+            assert_eq!(
+                large.checked_sub(&ConvPoly::constant(-1)),
+                Err(NtruError::Overflow)
+            );
+            assert_eq!(large.checked_mul(&small, 3), Err(NtruError::Overflow));
+
+            // The unchecked operations wrap around rather than panicking
+            assert_eq!(
+                large.add(&ConvPoly::constant(1)).coeffs,
+                vec![i32::MIN, i32::MIN]
+            );
         }
 
         #[test]
@@ -633,6 +703,13 @@ mod tests {
                 expected_remainder.coeffs, remainder.coeffs,
                 "Division remainder failed"
             );
+
+            // Division by zero
+            assert_eq!(
+                poly1.div_mod(&ConvPoly::constant(0), 2, 5),
+                Err(NtruError::ZeroDivisor),
+                "Division by zero should be an error"
+            );
         }
 
         #[test]
@@ -745,10 +822,10 @@ mod tests {
                 for _ in 0..n {
                     poly.coeffs.push(rng.gen_range(-1000..=1000));
                 }
-                poly = poly.modulo(m).trim();
+                poly = poly.modulo(m).unwrap().trim();
 
                 if let Ok(inverse) = poly.inverse(m, n) {
-                    let product = poly.mul(&inverse, n).modulo(m);
+                    let product = poly.mul(&inverse, n).modulo(m).unwrap();
                     assert_eq!(ConvPoly::constant(1), product, "Inverse failed");
                     num_inverse_found += 1;
                 } else {
@@ -763,46 +840,673 @@ mod tests {
         }
     }
 
+    mod inverse_mod_pow2_tests {
+        use super::*;
+
+        #[test]
+        fn test_inverse_mod_pow2() {
+            // Example in the ring (Z/8Z)[x]/(x^5 - 1)
+            let poly = ConvPoly {
+                coeffs: vec![1, 1, 0, 0, 1], // x^4 + x + 1
+            };
+            let inverse = poly.inverse_mod_pow2(3, 5).unwrap();
+            assert_eq!(
+                ConvPoly::constant(1),
+                poly.mul(&inverse, 5).modulo(8).unwrap(),
+                "Inverse modulo 8 failed"
+            );
+
+            // Not invertible modulo 2, so not invertible modulo 2^k either
+            let poly = ConvPoly {
+                coeffs: vec![1, 1], // x + 1
+            };
+            assert!(
+                poly.inverse_mod_pow2(11, 5).is_err(),
+                "x + 1 should not be invertible modulo 2048"
+            );
+
+            // Exponents outside of [1, 30] are rejected rather than overflowing
+            assert!(matches!(
+                poly.inverse_mod_pow2(0, 5),
+                Err(NtruError::InvalidParameters(_))
+            ));
+            assert!(matches!(
+                poly.inverse_mod_pow2(31, 5),
+                Err(NtruError::InvalidParameters(_))
+            ));
+
+            // Test random ternary polynomials
+            let num_tests = 50;
+            let mut rng = rand::thread_rng();
+            let mut num_inverse_found = 0;
+
+            for _ in 0..num_tests {
+                let (n, k) = (rng.gen_range(2..=40), rng.gen_range(1..=13));
+                let num_ones = rng.gen_range(1..=n / 2);
+                let poly = ternary_polynomial(n, num_ones, num_ones - 1).unwrap();
+
+                if let Ok(inverse) = poly.inverse_mod_pow2(k, n) {
+                    let product = poly.mul(&inverse, n).modulo(1 << k).unwrap();
+                    assert_eq!(ConvPoly::constant(1), product, "Inverse failed");
+                    num_inverse_found += 1;
+                }
+            }
+
+            println!(
+                "Found inverses for {} polynomials out of {}",
+                num_inverse_found, num_tests
+            );
+        }
+    }
+
+    mod inverse_constant_time_tests {
+        use super::*;
+
+        #[test]
+        fn test_matches_euclidean_inverse() {
+            let mut rng = rand::thread_rng();
+            for m in [2, 3, 5, 7, 383, 2053, 65537] {
+                let mut num_inverse_found = 0;
+                for _ in 0..40 {
+                    let n = rng.gen_range(1..=30);
+                    // Random polynomials, which are often zero divisors for small moduli
+                    let poly = ConvPoly {
+                        coeffs: (0..rng.gen_range(0..=n))
+                            .map(|_| rng.gen_range(-1000..=1000))
+                            .collect(),
+                    };
+
+                    let expected = poly.modulo(m).unwrap().inverse(m, n);
+                    let inverse = poly.inverse_constant_time(m, n);
+                    // Every coefficient is kept, zeros and all
+                    if let Ok(inverse) = &inverse {
+                        assert_eq!(inverse.coeffs.len(), n);
+                    }
+                    assert_eq!(
+                        expected,
+                        inverse.map(|inverse| inverse.trim()),
+                        "(Z/{}Z)[x]/(x^{} - 1): {}",
+                        m,
+                        n,
+                        poly
+                    );
+                    num_inverse_found += expected.is_ok() as usize;
+                }
+                println!(
+                    "Found inverses for {} polynomials modulo {}",
+                    num_inverse_found, m
+                );
+            }
+        }
+
+        #[test]
+        fn test_standard_sizes() {
+            // f(x) in T(d + 1, d) modulo p = 3 and the prime q = 383 of the default parameter set
+            let (n, d) = (661, 21);
+            let f = ternary_polynomial(n, d + 1, d).unwrap();
+            for m in [3, 383] {
+                match f.inverse_constant_time(m, n) {
+                    Ok(inverse) => {
+                        assert_eq!(inverse.coeffs.len(), n);
+                        assert_eq!(inverse.trim(), f.inverse(m, n).unwrap());
+                        assert_eq!(f.mul_mod(&inverse, n, m).unwrap(), ConvPoly::constant(1));
+                    }
+                    Err(err) => assert_eq!(Err(err), f.inverse(m, n)),
+                }
+            }
+        }
+
+        #[test]
+        fn test_invalid_modulus() {
+            let poly = ConvPoly::constant(1);
+            assert!(matches!(
+                poly.inverse_constant_time(2048, 5),
+                Err(NtruError::InvalidParameters(_))
+            ));
+            assert!(matches!(
+                poly.inverse_constant_time(3, 0),
+                Err(NtruError::InvalidParameters(_))
+            ));
+        }
+    }
+
+    mod inverse_mod_prime_power_tests {
+        use super::*;
+
+        #[test]
+        fn test_inverse_mod_prime_power() {
+            let mut rng = rand::thread_rng();
+            for (p, k) in [(2, 11), (3, 5), (5, 3), (7, 2), (3, 19)] {
+                let m = i32::pow(p, k);
+                let mut num_inverse_found = 0;
+                for _ in 0..20 {
+                    let n = rng.gen_range(2..=40);
+                    let num_ones = rng.gen_range(1..=n / 2);
+                    let poly = ternary_polynomial(n, num_ones, num_ones - 1).unwrap();
+
+                    if let Ok(inverse) = poly.inverse_mod_prime_power(p, k, n) {
+                        assert_eq!(
+                            ConvPoly::constant(1),
+                            poly.mul_mod(&inverse, n, m).unwrap(),
+                            "Inverse modulo {}^{} failed",
+                            p,
+                            k
+                        );
+                        // Every coefficient is kept, and inverse() recognizes the prime power and lifts too
+                        assert_eq!(inverse.coeffs.len(), n);
+                        assert_eq!(inverse.trim(), poly.inverse(m, n).unwrap());
+                        num_inverse_found += 1;
+                    }
+                }
+                println!(
+                    "Found inverses for {} polynomials modulo {}",
+                    num_inverse_found, m
+                );
+            }
+        }
+
+        #[test]
+        fn test_inverse_mod_prime_power_invalid() {
+            let poly = ConvPoly {
+                coeffs: vec![1, 1, 0, 0, 1],
+            };
+            for (p, k) in [(4, 2), (1, 3), (3, 0), (3, 20)] {
+                assert!(matches!(
+                    poly.inverse_mod_prime_power(p, k, 5),
+                    Err(NtruError::InvalidParameters(_))
+                ));
+            }
+
+            // x + 1 vanishes at x = 1, so it's a zero divisor modulo every prime power
+            let poly = ConvPoly { coeffs: vec![1, 1] };
+            assert_eq!(poly.inverse(9, 4), Err(NtruError::NotInvertible));
+        }
+    }
+
+    mod inverse_composite_tests {
+        use super::*;
+
+        #[test]
+        fn test_inverse_composite_modulus() {
+            // Example in the ring (Z/15Z)[x]/(x^5 - 1), where neither 3 nor 5 is a unit
+            let poly = ConvPoly {
+                coeffs: vec![2, 1, 0, 0, 1], // x^4 + x + 2
+            };
+            let inverse = poly.inverse(15, 5).unwrap();
+            assert_eq!(
+                poly.mul_mod(&inverse, 5, 15).unwrap(),
+                ConvPoly::constant(1)
+            );
+
+            let mut rng = rand::thread_rng();
+            for m in [6, 12, 15, 30, 1000, 2 * 3 * 5 * 7 * 11 * 13, 65_536 * 3] {
+                let mut num_inverse_found = 0;
+                for _ in 0..30 {
+                    let n = rng.gen_range(1..=30);
+                    let poly = ConvPoly {
+                        coeffs: (0..n).map(|_| rng.gen_range(-1000..=1000)).collect(),
+                    };
+
+                    // A polynomial is invertible modulo m exactly when it is modulo every prime dividing m
+                    let invertible_mod_primes = (2..=m)
+                        .filter(|&p| m % p == 0 && is_prime(p))
+                        .all(|p| poly.inverse(p, n).is_ok());
+                    match poly.inverse(m, n) {
+                        Ok(inverse) => {
+                            assert!(invertible_mod_primes);
+                            assert_eq!(
+                                poly.mul_mod(&inverse, n, m).unwrap(),
+                                ConvPoly::constant(1),
+                                "Inverse modulo {} failed",
+                                m
+                            );
+                            num_inverse_found += 1;
+                        }
+                        Err(err) => {
+                            assert_eq!(err, NtruError::NotInvertible);
+                            assert!(!invertible_mod_primes);
+                        }
+                    }
+                }
+                println!(
+                    "Found inverses for {} polynomials modulo {}",
+                    num_inverse_found, m
+                );
+            }
+        }
+    }
+
+    mod resultant_tests {
+        use super::*;
+        use ntru_rs::ReductionPoly;
+
+        #[test]
+        fn test_resultant() {
+            // Res(x^3 - 1, x - 2) is the product of ζ - 2 over the cube roots of unity ζ, -(2^3 - 1)
+            let poly = ConvPoly {
+                coeffs: vec![-2, 1],
+            };
+            assert_eq!(poly.resultant(&ReductionPoly::Cyclic(3)), Ok(-7));
+            assert_eq!(poly.resultant_mod(&ReductionPoly::Cyclic(3), 5), Ok(3));
+            // Res(x^5 - 1, 3) = 3^5
+            let constant = ConvPoly::constant(3);
+            assert_eq!(constant.resultant(&ReductionPoly::Cyclic(5)), Ok(243));
+            // x + 1 divides x^4 - 1 and x^3 + 1
+            let poly = ConvPoly { coeffs: vec![1, 1] };
+            assert_eq!(poly.resultant(&ReductionPoly::Cyclic(4)), Ok(0));
+            assert_eq!(poly.resultant(&ReductionPoly::Negacyclic(3)), Ok(0));
+            assert_eq!(poly.resultant_mod(&ReductionPoly::Cyclic(4), 7), Ok(0));
+            assert_eq!(
+                ConvPoly::constant(0).resultant(&ReductionPoly::Cyclic(4)),
+                Ok(0)
+            );
+
+            // Res(x^3 - 1, x - 2) = -7 modulo a prime power and a composite modulus
+            let poly = ConvPoly {
+                coeffs: vec![-2, 1],
+            };
+            assert_eq!(poly.resultant_mod(&ReductionPoly::Cyclic(3), 8), Ok(1));
+            assert_eq!(
+                poly.resultant_mod(&ReductionPoly::Cyclic(3), 2048),
+                Ok(2041)
+            );
+            assert_eq!(poly.resultant_mod(&ReductionPoly::Cyclic(3), 63), Ok(56));
+            assert!(matches!(
+                poly.resultant_mod(&ReductionPoly::Cyclic(4), 1),
+                Err(NtruError::InvalidParameters(_))
+            ));
+            assert!(matches!(
+                poly.resultant(&ReductionPoly::Cyclotomic(1)),
+                Err(NtruError::InvalidParameters(_))
+            ));
+            // Far too large for an i128
+            let large = ternary_polynomial(661, 22, 21).unwrap();
+            assert_eq!(
+                large.resultant(&ReductionPoly::Cyclic(661)),
+                Err(NtruError::Overflow)
+            );
+        }
+
+        #[test]
+        fn test_resultant_mod_matches_resultant() {
+            let mut rng = rand::thread_rng();
+            for _ in 0..100 {
+                let n = rng.gen_range(1..=12);
+                let poly = ConvPoly {
+                    coeffs: (0..rng.gen_range(1..=2 * n))
+                        .map(|_| rng.gen_range(-3..=3))
+                        .collect(),
+                };
+                for reduction in [
+                    ReductionPoly::Cyclic(n),
+                    ReductionPoly::Negacyclic(n),
+                    ReductionPoly::Cyclotomic(n + 1),
+                ] {
+                    let resultant = poly.resultant(&reduction).unwrap();
+                    for m in [
+                        2,
+                        3,
+                        4,
+                        5,
+                        7,
+                        9,
+                        12,
+                        383,
+                        2048,
+                        3375,
+                        1 << 30,
+                        2_147_483_646,
+                        2_147_483_647,
+                    ] {
+                        assert_eq!(
+                            poly.resultant_mod(&reduction, m).unwrap() as i128,
+                            resultant.rem_euclid(m as i128),
+                            "Res({}, {}) mod {}",
+                            reduction,
+                            poly,
+                            m
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_cyclotomic_factors() {
+            let factors: Vec<Vec<i32>> = ReductionPoly::cyclotomic_factors(6)
+                .iter()
+                .map(ReductionPoly::coeffs)
+                .collect();
+            assert_eq!(
+                factors,
+                vec![vec![-1, 1], vec![1, 1], vec![1, 1, 1], vec![1, -1, 1]]
+            );
+
+            let mut rng = rand::thread_rng();
+            for n in 1..=30 {
+                // The factors multiply to x^n - 1
+                let factors = ReductionPoly::cyclotomic_factors(n);
+                let product = factors.iter().fold(ConvPoly::constant(1), |acc, f| {
+                    acc.mul(&ConvPoly { coeffs: f.coeffs() }, n + 1)
+                });
+                assert_eq!(product.coeffs, ReductionPoly::Cyclic(n).coeffs());
+
+                // and so does the resultant of a polynomial with each of them
+                if n <= 12 {
+                    let poly = ConvPoly {
+                        coeffs: (0..n).map(|_| rng.gen_range(-2..=2)).collect(),
+                    };
+                    let product: i128 =
+                        factors.iter().map(|f| poly.resultant(f).unwrap()).product();
+                    assert_eq!(poly.resultant(&ReductionPoly::Cyclic(n)), Ok(product));
+                }
+            }
+        }
+
+        #[test]
+        fn test_is_invertible() {
+            let mut rng = rand::thread_rng();
+            for m in [2, 3, 15, 383, 2048] {
+                for _ in 0..50 {
+                    let n = rng.gen_range(1..=40);
+                    let poly = ConvPoly {
+                        coeffs: (0..n).map(|_| rng.gen_range(-1..=1)).collect(),
+                    };
+                    assert_eq!(
+                        poly.is_invertible(m, n),
+                        poly.inverse(m, n).is_ok(),
+                        "{} modulo {}",
+                        poly,
+                        m
+                    );
+                }
+            }
+            assert!(!ConvPoly::constant(1).is_invertible(1, 5));
+            assert!(!ConvPoly::constant(1).is_invertible(3, 0));
+        }
+
+        #[test]
+        fn test_shared_factor() {
+            // 1 + x + x^2 is Φ_3(x) itself, and x + 1 ≡ x - 1 = Φ_1(x) (mod 2)
+            let poly = ConvPoly {
+                coeffs: vec![1, 1, 1],
+            };
+            assert_eq!(poly.shared_factor(2, 3), Some((2, 3)));
+            assert_eq!(poly.shared_factor(5 * 32, 3), Some((2, 3)));
+            assert_eq!(poly.shared_factor(3, 3), Some((3, 1)));
+            let poly = ConvPoly { coeffs: vec![1, 1] };
+            assert_eq!(poly.shared_factor(2048, 4), Some((2, 1)));
+            assert_eq!(poly.shared_factor(3, 4), Some((3, 2)));
+
+            let mut rng = rand::thread_rng();
+            for m in [2, 3, 15, 2048] {
+                for _ in 0..50 {
+                    let n = rng.gen_range(1..=30);
+                    let poly = ConvPoly {
+                        coeffs: (0..n).map(|_| rng.gen_range(-1..=1)).collect(),
+                    };
+                    assert_eq!(
+                        poly.shared_factor(m, n).is_none(),
+                        poly.is_invertible(m, n),
+                        "{} modulo {}",
+                        poly,
+                        m
+                    );
+                }
+            }
+            assert_eq!(ConvPoly::constant(0).shared_factor(1, 5), None);
+            assert_eq!(ConvPoly::constant(0).shared_factor(3, 0), None);
+        }
+    }
+
+    mod rotation_tests {
+        use super::*;
+
+        fn random_poly(len: usize, bound: i32) -> ConvPoly {
+            let mut rng = rand::thread_rng();
+            ConvPoly {
+                coeffs: (0..len).map(|_| rng.gen_range(-bound..=bound)).collect(),
+            }
+        }
+
+        #[test]
+        fn test_rotate() {
+            // x * (1 + 2x + 3x^4) = 3 + x + 2x^2 in Z[x]/(x^5 - 1)
+            let poly = ConvPoly {
+                coeffs: vec![1, 2, 0, 0, 3],
+            };
+            assert_eq!(poly.rotate(1, 5).unwrap().coeffs, vec![3, 1, 2]);
+            assert_eq!(poly.rotate(-1, 5).unwrap().coeffs, vec![2, 0, 0, 3, 1]);
+            assert_eq!(poly.rotate(5, 5).unwrap(), poly);
+            // Coefficients of degree n and above are folded first
+            let long = ConvPoly {
+                coeffs: vec![1, 0, 0, 2],
+            };
+            assert_eq!(long.rotate(0, 3).unwrap().coeffs, vec![3]);
+
+            for n in 1..=20 {
+                let poly = random_poly(n, 5);
+                for k in -25isize..=25 {
+                    let mut monomial = ConvPoly {
+                        coeffs: vec![0; k.rem_euclid(n as isize) as usize + 1],
+                    };
+                    *monomial.coeffs.last_mut().unwrap() = 1;
+                    assert_eq!(poly.rotate(k, n).unwrap(), poly.mul(&monomial, n));
+                }
+            }
+            assert!(matches!(
+                poly.rotate(1, 0),
+                Err(NtruError::InvalidParameters(_))
+            ));
+        }
+
+        #[test]
+        fn test_automorphism() {
+            // x --> x^2 takes 1 + 2x + 3x^4 to 1 + 2x^2 + 3x^8 = 1 + 2x^2 + 3x^3 in Z[x]/(x^5 - 1)
+            let poly = ConvPoly {
+                coeffs: vec![1, 2, 0, 0, 3],
+            };
+            assert_eq!(poly.automorphism(2, 5).unwrap().coeffs, vec![1, 0, 2, 3]);
+            assert_eq!(poly.automorphism(6, 5).unwrap(), poly);
+            assert_eq!(poly.conjugate(5).unwrap().coeffs, vec![1, 3, 0, 0, 2]);
+            assert!(matches!(
+                poly.automorphism(3, 6),
+                Err(NtruError::InvalidParameters(_))
+            ));
+            assert!(matches!(
+                poly.automorphism(0, 5),
+                Err(NtruError::InvalidParameters(_))
+            ));
+
+            // Automorphisms preserve sums and products, and the conjugate is x --> x^(n - 1)
+            for n in [1, 2, 7, 11, 12, 30] {
+                let (a, b) = (random_poly(n, 3), random_poly(n, 3));
+                for k in (1..2 * n).filter(|&k| gcd(k as i32, n as i32) == Ok(1)) {
+                    let sigma = |p: &ConvPoly| p.automorphism(k, n).unwrap();
+                    assert_eq!(sigma(&a.add(&b)), sigma(&a).add(&sigma(&b)));
+                    assert_eq!(sigma(&a.mul(&b, n)), sigma(&a).mul(&sigma(&b), n));
+                }
+                assert_eq!(a.conjugate(n), a.automorphism(n - 1, n));
+                assert_eq!(a.conjugate(n).unwrap().conjugate(n).unwrap(), a.trim());
+            }
+        }
+
+        #[test]
+        fn test_rotated_keys_are_equivalent() {
+            // f(x) and x^k f(x) give the same public key when g(x) is rotated alongside them
+            let (n, q) = (61, 383);
+            let g = ternary_polynomial(n, 10, 10).unwrap();
+            let f = loop {
+                let f = ternary_polynomial(n, 11, 10).unwrap();
+                if f.is_invertible(q, n) {
+                    break f;
+                }
+            };
+            let h = f.inverse(q, n).unwrap().mul_mod(&g, n, q).unwrap();
+            for k in [1, 5, -17] {
+                let (f_k, g_k) = (f.rotate(k, n).unwrap(), g.rotate(k, n).unwrap());
+                let f_k_inv = f_k.inverse(q, n).unwrap();
+                assert_eq!(f_k_inv.mul_mod(&g_k, n, q).unwrap(), h);
+                // (x^k f(x))^-1 = x^-k f(x)^-1
+                assert_eq!(f_k_inv, f.inverse(q, n).unwrap().rotate(-k, n).unwrap());
+            }
+        }
+
+        #[test]
+        fn test_pow_mod() {
+            let mut rng = rand::thread_rng();
+            for n in [1, 5, 16, 61] {
+                for m in [2, 3, 383, 2048] {
+                    let poly = random_poly(n, 100);
+                    let e = rng.gen_range(0..40);
+                    let expected = (0..e)
+                        .fold(ConvPoly::constant(1).modulo(m).unwrap(), |acc, _| {
+                            acc.mul_mod(&poly, n, m).unwrap()
+                        });
+                    assert_eq!(poly.pow_mod(e, n, m).unwrap(), expected);
+                }
+            }
+
+            // ord_7(3) = 6, so (Z/3Z)[x]/(x^7 - 1) = F_3 x F_729 and its units form a group of order 2 * 728
+            let poly = ConvPoly {
+                coeffs: vec![1, 0, 0, 1],
+            };
+            assert!(poly.is_invertible(3, 7));
+            assert_eq!(poly.pow_mod(2 * 728, 7, 3), Ok(ConvPoly::constant(1)));
+            assert_eq!(poly.pow_mod(2 * 728 - 1, 7, 3), poly.inverse(3, 7));
+            assert_eq!(poly.pow_mod(0, 7, 1), Ok(ConvPoly::constant(0)));
+            assert!(matches!(
+                poly.pow_mod(2, 7, 0),
+                Err(NtruError::InvalidModulus(0))
+            ));
+        }
+
+        #[test]
+        fn test_eval_mod() {
+            // 1 + 2x + 3x^4 at x = 2 is 1 + 4 + 48 = 53
+            let poly = ConvPoly {
+                coeffs: vec![1, 2, 0, 0, 3],
+            };
+            assert_eq!(poly.eval_mod(2, 1000), Ok(53));
+            assert_eq!(poly.eval_mod(-2, 7), Ok((1 - 4 + 48) % 7));
+            assert_eq!(ConvPoly::constant(0).eval_mod(5, 7), Ok(0));
+            assert!(matches!(
+                poly.eval_mod(2, -3),
+                Err(NtruError::InvalidModulus(-3))
+            ));
+
+            // 3^5 ≡ 1 (mod 11), so evaluating at 3 is a ring homomorphism from Z[x]/(x^5 - 1) to Z/11Z
+            for _ in 0..50 {
+                let (a, b) = (random_poly(5, 1000), random_poly(5, 1000));
+                let product = a.mul_mod(&b, 5, 11).unwrap();
+                let expected = a.eval_mod(3, 11).unwrap() * b.eval_mod(3, 11).unwrap() % 11;
+                assert_eq!(product.eval_mod(3, 11), Ok(expected));
+            }
+        }
+    }
+
+    mod norm_tests {
+        use super::*;
+
+        #[test]
+        fn test_norms() {
+            // 3 - 4x + x^3
+            let poly = ConvPoly {
+                coeffs: vec![3, -4, 0, 1, 0],
+            };
+            assert_eq!(poly.weight(), 3);
+            assert_eq!(poly.l1_norm(), 8);
+            assert_eq!(poly.l2_norm(), 26f64.sqrt());
+            assert_eq!(poly.linf_norm(), 4);
+            // The mean of its 4 coefficients is 0, so the centered norm is the L2 norm
+            assert_eq!(poly.centered_norm(4), Ok(26f64.sqrt()));
+            // Folding into Z[x]/(x^3 - 1) gives 4 - 4x, whose mean is also 0
+            assert_eq!(poly.centered_norm(3), Ok(32f64.sqrt()));
+
+            let zero = ConvPoly::constant(0);
+            assert_eq!((zero.weight(), zero.l1_norm(), zero.linf_norm()), (0, 0, 0));
+            assert_eq!(zero.l2_norm(), 0.0);
+            let extreme = ConvPoly {
+                coeffs: vec![i32::MIN, i32::MAX],
+            };
+            assert_eq!(extreme.linf_norm(), 1 << 31);
+            assert_eq!(extreme.l1_norm(), (1 << 32) - 1);
+            assert!(matches!(
+                poly.centered_norm(0),
+                Err(NtruError::InvalidParameters(_))
+            ));
+        }
+
+        #[test]
+        fn test_centered_norm() {
+            // Constant polynomials 1 + x + ... + x^(n - 1) have centered norm 0
+            assert_eq!(ConvPoly { coeffs: vec![5; 7] }.centered_norm(7), Ok(0.0));
+
+            // sqrt(d1 + d2 - (d1 - d2)^2 / n) for polynomials in T(d1, d2)
+            for (n, d1, d2) in [(11, 4, 3), (61, 10, 10), (661, 22, 21), (20, 15, 0)] {
+                let poly = ternary_polynomial(n, d1, d2).unwrap();
+                let diff = d1 as f64 - d2 as f64;
+                let expected = ((d1 + d2) as f64 - diff * diff / n as f64).sqrt();
+                assert!((poly.centered_norm(n).unwrap() - expected).abs() < 1e-9);
+                assert_eq!(poly.l1_norm(), (d1 + d2) as u64);
+            }
+
+            // Adding a multiple of 1 + x + ... + x^(n - 1) doesn't change the centered norm, and never makes it
+            // larger than the L2 norm
+            let mut rng = rand::thread_rng();
+            for _ in 0..50 {
+                let n = rng.gen_range(1..=30);
+                let poly = ConvPoly {
+                    coeffs: (0..n).map(|_| rng.gen_range(-50..=50)).collect(),
+                };
+                let shifted = poly.add(&ConvPoly {
+                    coeffs: vec![rng.gen_range(-9..=9); n],
+                });
+                let norm = poly.centered_norm(n).unwrap();
+                assert!((shifted.centered_norm(n).unwrap() - norm).abs() < 1e-9);
+                assert!(norm <= poly.l2_norm() + 1e-9);
+            }
+        }
+    }
+
     mod integer_tests {
         use super::*;
 
         #[test]
         fn test_gcd() {
             // Test with positive numbers
-            assert_eq!(gcd(48, 18), 6, "GCD of 48 and 18 should be 6");
-            assert_eq!(gcd(101, 103), 1, "GCD of two primes should be 1");
-            assert_eq!(gcd(2, 4), 2, "GCD of 2 and 4 should be 2");
-            assert_eq!(gcd(72, 36), 36, "GCD of 72 and 36 should be 36");
-            assert_eq!(gcd(72, 54), 18, "GCD of 72 and 54 should be 18");
+            assert_eq!(gcd(48, 18), Ok(6), "GCD of 48 and 18 should be 6");
+            assert_eq!(gcd(101, 103), Ok(1), "GCD of two primes should be 1");
+            assert_eq!(gcd(2, 4), Ok(2), "GCD of 2 and 4 should be 2");
+            assert_eq!(gcd(72, 36), Ok(36), "GCD of 72 and 36 should be 36");
+            assert_eq!(gcd(72, 54), Ok(18), "GCD of 72 and 54 should be 18");
 
             // Test with two negative numbers
-            assert_eq!(gcd(-48, -18), 6, "GCD of -48 and -18 should be 6");
-            assert_eq!(gcd(-101, -103), 1, "GCD of two primes should be 1");
-            assert_eq!(gcd(-2, -4), 2, "GCD of -2 and -4 should be 2");
-            assert_eq!(gcd(-72, -36), 36, "GCD of -72 and -36 should be 36");
-            assert_eq!(gcd(-72, -54), 18, "GCD of -72 and -54 should be 18");
+            assert_eq!(gcd(-48, -18), Ok(6), "GCD of -48 and -18 should be 6");
+            assert_eq!(gcd(-101, -103), Ok(1), "GCD of two primes should be 1");
+            assert_eq!(gcd(-2, -4), Ok(2), "GCD of -2 and -4 should be 2");
+            assert_eq!(gcd(-72, -36), Ok(36), "GCD of -72 and -36 should be 36");
+            assert_eq!(gcd(-72, -54), Ok(18), "GCD of -72 and -54 should be 18");
 
             // Test with one positive and one negative number
-            assert_eq!(gcd(-48, 18), 6, "GCD of -48 and 18 should be 6");
-            assert_eq!(gcd(101, -103), 1, "GCD of 101 and -103 should be 1");
-            assert_eq!(gcd(-2, 4), 2, "GCD of -2 and 4 should be 2");
-            assert_eq!(gcd(72, -36), 36, "GCD of 72 and -36 should be 36");
-            assert_eq!(gcd(-72, 54), 18, "GCD of -72 and 54 should be 18");
+            assert_eq!(gcd(-48, 18), Ok(6), "GCD of -48 and 18 should be 6");
+            assert_eq!(gcd(101, -103), Ok(1), "GCD of 101 and -103 should be 1");
+            assert_eq!(gcd(-2, 4), Ok(2), "GCD of -2 and 4 should be 2");
+            assert_eq!(gcd(72, -36), Ok(36), "GCD of 72 and -36 should be 36");
+            assert_eq!(gcd(-72, 54), Ok(18), "GCD of -72 and 54 should be 18");
 
             // Test with both a and b being zero
-            assert!(
-                std::panic::catch_unwind(|| {
-                    let _ = gcd(0, 0);
-                })
-                .is_err(),
-                "GCD of 0 and 0 should panic"
+            assert_eq!(
+                gcd(0, 0),
+                Err(NtruError::ZeroDivisor),
+                "GCD of 0 and 0 should be an error"
             );
 
             // Test with a being zero and b being non-zero
-            assert_eq!(gcd(0, 5), 5, "GCD of 0 and 5 should be 5");
+            assert_eq!(gcd(0, 5), Ok(5), "GCD of 0 and 5 should be 5");
 
             // Test with a being non-zero and b being zero
-            assert_eq!(gcd(5, 0), 5, "GCD of 5 and 0 should be 5");
+            assert_eq!(gcd(5, 0), Ok(5), "GCD of 5 and 0 should be 5");
         }
 
         #[test]
@@ -813,18 +1517,18 @@ mod tests {
             // Test with random positive numbers
             for _ in 0..num_tests {
                 let (a, b) = (rng.gen_range(1..=1000), rng.gen_range(1..=1000));
-                let (d, x, y) = extended_gcd(a, b);
+                let (d, x, y) = extended_gcd(a, b).unwrap();
 
-                assert_eq!(d, gcd(a, b), "GCD calculation failed");
+                assert_eq!(Ok(d), gcd(a, b), "GCD calculation failed");
                 assert_eq!(d, a * x + b * y, "Extended GCD calculation failed");
             }
 
             // Test with one random positive and one random negative number
             for _ in 0..num_tests {
                 let (a, b) = (rng.gen_range(-1000..=-1), rng.gen_range(1..=1000));
-                let (d, x, y) = extended_gcd(a, b);
+                let (d, x, y) = extended_gcd(a, b).unwrap();
 
-                assert_eq!(d, gcd(a, b), "GCD calculation failed");
+                assert_eq!(Ok(d), gcd(a, b), "GCD calculation failed");
                 assert_eq!(
                     d,
                     a.abs() * x + b.abs() * y,
@@ -833,9 +1537,9 @@ mod tests {
             }
             for _ in 0..num_tests {
                 let (a, b) = (rng.gen_range(1..=1000), rng.gen_range(-1000..=-1));
-                let (d, x, y) = extended_gcd(a, b);
+                let (d, x, y) = extended_gcd(a, b).unwrap();
 
-                assert_eq!(d, gcd(a, b), "GCD calculation failed");
+                assert_eq!(Ok(d), gcd(a, b), "GCD calculation failed");
                 assert_eq!(
                     d,
                     a.abs() * x + b.abs() * y,
@@ -846,9 +1550,9 @@ mod tests {
             // Test with both random negative numbers
             for _ in 0..num_tests {
                 let (a, b) = (rng.gen_range(-1000..=-1), rng.gen_range(-1000..=-1));
-                let (d, x, y) = extended_gcd(a, b);
+                let (d, x, y) = extended_gcd(a, b).unwrap();
 
-                assert_eq!(d, gcd(a, b), "GCD calculation failed");
+                assert_eq!(Ok(d), gcd(a, b), "GCD calculation failed");
                 assert_eq!(
                     d,
                     a.abs() * x + b.abs() * y,
@@ -857,28 +1561,38 @@ mod tests {
             }
 
             // Test with both a and b being zero
-            let (a, b) = (0, 0);
-            assert!(
-                std::panic::catch_unwind(|| {
-                    let _ = extended_gcd(a, b);
-                })
-                .is_err(),
-                "Extended GCD of 0 and 0 should panic"
+            assert_eq!(
+                extended_gcd(0, 0),
+                Err(NtruError::ZeroDivisor),
+                "Extended GCD of 0 and 0 should be an error"
             );
 
             // Test with a being zero and b being non-zero
             let (a, b) = (0, rng.gen_range(1..=1000));
-            let (d, x, y) = extended_gcd(a, b);
+            let (d, x, y) = extended_gcd(a, b).unwrap();
             assert_eq!(d, b, "GCD calculation failed");
             assert_eq!(d, a * x + b * y, "Extended GCD calculation failed");
 
             // Test with a being non-zero and b being zero
             let (a, b) = (rng.gen_range(1..=1000), 0);
-            let (d, x, y) = extended_gcd(a, b);
+            let (d, x, y) = extended_gcd(a, b).unwrap();
             assert_eq!(d, a, "GCD calculation failed");
             assert_eq!(d, a * x + b * y, "Extended GCD calculation failed");
         }
 
+        #[test]
+        fn test_is_prime() {
+            let primes = [2, 3, 5, 7, 11, 383, 401, 661, 821, 2147483647];
+            for p in primes {
+                assert!(is_prime(p), "{} should be prime", p);
+            }
+
+            let non_primes = [-7, 0, 1, 4, 9, 15, 660, 2048, 4096, 2147483646];
+            for a in non_primes {
+                assert!(!is_prime(a), "{} should not be prime", a);
+            }
+        }
+
         #[test]
         fn test_inverse_modulo_m() {
             // Prime modulus (so that all elements have inverses)
@@ -914,34 +1628,34 @@ mod tests {
         #[test]
         fn test_center_lift() {
             // Test cases with an odd modulus
-            assert_eq!(center_lift(-4, 7), 3, "Center lift failed");
-            assert_eq!(center_lift(-3, 7), -3, "Center lift failed");
-            assert_eq!(center_lift(-2, 7), -2, "Center lift failed");
-            assert_eq!(center_lift(-1, 7), -1, "Center lift failed");
-            assert_eq!(center_lift(0, 7), 0, "Center lift failed");
-            assert_eq!(center_lift(1, 7), 1, "Center lift failed");
-            assert_eq!(center_lift(2, 7), 2, "Center lift failed");
-            assert_eq!(center_lift(3, 7), 3, "Center lift failed");
-            assert_eq!(center_lift(4, 7), -3, "Center lift failed");
-            assert_eq!(center_lift(5, 7), -2, "Center lift failed");
-            assert_eq!(center_lift(6, 7), -1, "Center lift failed");
-            assert_eq!(center_lift(7, 7), 0, "Center lift failed");
+            assert_eq!(center_lift(-4, 7), Ok(3), "Center lift failed");
+            assert_eq!(center_lift(-3, 7), Ok(-3), "Center lift failed");
+            assert_eq!(center_lift(-2, 7), Ok(-2), "Center lift failed");
+            assert_eq!(center_lift(-1, 7), Ok(-1), "Center lift failed");
+            assert_eq!(center_lift(0, 7), Ok(0), "Center lift failed");
+            assert_eq!(center_lift(1, 7), Ok(1), "Center lift failed");
+            assert_eq!(center_lift(2, 7), Ok(2), "Center lift failed");
+            assert_eq!(center_lift(3, 7), Ok(3), "Center lift failed");
+            assert_eq!(center_lift(4, 7), Ok(-3), "Center lift failed");
+            assert_eq!(center_lift(5, 7), Ok(-2), "Center lift failed");
+            assert_eq!(center_lift(6, 7), Ok(-1), "Center lift failed");
+            assert_eq!(center_lift(7, 7), Ok(0), "Center lift failed");
 
             // Test cases with an even modulus
-            assert_eq!(center_lift(-5, 8), 3, "Center lift failed");
-            assert_eq!(center_lift(-4, 8), 4, "Center lift failed");
-            assert_eq!(center_lift(-3, 8), -3, "Center lift failed");
-            assert_eq!(center_lift(-2, 8), -2, "Center lift failed");
-            assert_eq!(center_lift(-1, 8), -1, "Center lift failed");
-            assert_eq!(center_lift(0, 8), 0, "Center lift failed");
-            assert_eq!(center_lift(1, 8), 1, "Center lift failed");
-            assert_eq!(center_lift(2, 8), 2, "Center lift failed");
-            assert_eq!(center_lift(3, 8), 3, "Center lift failed");
-            assert_eq!(center_lift(4, 8), 4, "Center lift failed");
-            assert_eq!(center_lift(5, 8), -3, "Center lift failed");
-            assert_eq!(center_lift(6, 8), -2, "Center lift failed");
-            assert_eq!(center_lift(7, 8), -1, "Center lift failed");
-            assert_eq!(center_lift(8, 8), 0, "Center lift failed");
+            assert_eq!(center_lift(-5, 8), Ok(3), "Center lift failed");
+            assert_eq!(center_lift(-4, 8), Ok(4), "Center lift failed");
+            assert_eq!(center_lift(-3, 8), Ok(-3), "Center lift failed");
+            assert_eq!(center_lift(-2, 8), Ok(-2), "Center lift failed");
+            assert_eq!(center_lift(-1, 8), Ok(-1), "Center lift failed");
+            assert_eq!(center_lift(0, 8), Ok(0), "Center lift failed");
+            assert_eq!(center_lift(1, 8), Ok(1), "Center lift failed");
+            assert_eq!(center_lift(2, 8), Ok(2), "Center lift failed");
+            assert_eq!(center_lift(3, 8), Ok(3), "Center lift failed");
+            assert_eq!(center_lift(4, 8), Ok(4), "Center lift failed");
+            assert_eq!(center_lift(5, 8), Ok(-3), "Center lift failed");
+            assert_eq!(center_lift(6, 8), Ok(-2), "Center lift failed");
+            assert_eq!(center_lift(7, 8), Ok(-1), "Center lift failed");
+            assert_eq!(center_lift(8, 8), Ok(0), "Center lift failed");
 
             // Test random numbers
             let mut rng = rand::thread_rng();
@@ -950,7 +1664,7 @@ mod tests {
             for _ in 0..num_tests {
                 let a = rng.gen_range(-1000..=1000);
                 let m = rng.gen_range(1..=1000);
-                let result = center_lift(a, m);
+                let result = center_lift(a, m).unwrap();
 
                 assert_eq!(result.rem_euclid(m), a.rem_euclid(m), "Center lift failed");
                 assert!(result >= -m / 2, "Center lift failed");
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod decryption_failure_tests {
+    use ntru_rs::decryption_failure::{
+        analytic_failure_probability, monte_carlo_failure_probability, MessageDistribution,
+    };
+    use ntru_rs::{NtruError, NtruParams, ParamSet};
+
+    #[test]
+    fn test_message_distribution() {
+        // The uniform distribution covers the center-lifted residues (-p/2, p/2]
+        assert_eq!(
+            MessageDistribution::uniform(3).unwrap(),
+            MessageDistribution::ternary(1.0, 1.0, 1.0).unwrap()
+        );
+        assert_eq!(
+            MessageDistribution::uniform(4).unwrap(),
+            MessageDistribution::new(-1, vec![0.25; 4]).unwrap()
+        );
+
+        // Negative or all-zero probabilities don't form a distribution
+        assert!(MessageDistribution::new(0, vec![0.5, -0.5]).is_err());
+        assert!(MessageDistribution::ternary(0.0, 0.0, 0.0).is_err());
+        assert_eq!(
+            MessageDistribution::uniform(0),
+            Err(NtruError::InvalidModulus(0))
+        );
+    }
+
+    #[test]
+    fn test_invalid_estimates() {
+        let msg_dist = MessageDistribution::uniform(3).unwrap();
+        assert!(monte_carlo_failure_probability(&NtruParams::default(), &msg_dist, 0).is_err());
+        assert!(analytic_failure_probability(&NtruParams::new(0, 3, 127, 0), &msg_dist).is_err());
+    }
+
+    #[test]
+    fn test_worst_case_bound_never_fails() {
+        // q > (6d + 1)p, so every coefficient always lies inside (-q/2, q/2]
+        let params = NtruParams::default();
+        let msg_dist = MessageDistribution::uniform(params.p).unwrap();
+        let estimate = analytic_failure_probability(&params, &msg_dist).unwrap();
+        assert_eq!(estimate.per_coefficient, 0.0);
+        assert_eq!(estimate.per_message, 0.0);
+
+        let estimate = monte_carlo_failure_probability(&params, &msg_dist, 5).unwrap();
+        assert_eq!(estimate.per_coefficient, 0.0);
+        assert_eq!(estimate.per_message, 0.0);
+    }
+
+    #[test]
+    fn test_standardized_sets_rarely_fail() {
+        let params = ParamSet::Ees401Ep1.params();
+        let estimate =
+            analytic_failure_probability(&params, &MessageDistribution::uniform(3).unwrap())
+                .unwrap();
+        println!("ees401ep1: {:?}", estimate);
+        assert!(estimate.per_coefficient > 0.0);
+        assert!(estimate.per_message < 2f64.powi(-100));
+    }
+
+    #[test]
+    fn test_analytic_matches_monte_carlo() {
+        // A deliberately small q, so that failures are common enough to observe
+        let params = NtruParams::new(107, 3, 41, 12);
+        let msg_dist = MessageDistribution::uniform(params.p).unwrap();
+        let analytic = analytic_failure_probability(&params, &msg_dist).unwrap();
+        let sampled = monte_carlo_failure_probability(&params, &msg_dist, 400).unwrap();
+        println!("analytic: {:?}, monte carlo: {:?}", analytic, sampled);
+
+        let ratio = sampled.per_coefficient / analytic.per_coefficient;
+        assert!(
+            ratio > 0.7 && ratio < 1.3,
+            "Per-coefficient estimates differ too much"
+        );
+        assert!(
+            (sampled.per_message - analytic.per_message).abs() < 0.1,
+            "Per-message estimates differ too much"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod multiplication_tests {
+    use ntru_rs::{ConvPoly, MulBackend, PolyRing};
+    use rand::Rng;
+
+    const BACKENDS: [MulBackend; 4] = [
+        MulBackend::Karatsuba,
+        MulBackend::ToomCook4,
+        MulBackend::Ntt,
+        MulBackend::Auto,
+    ];
+
+    fn random_poly(len: usize, bound: i32) -> ConvPoly {
+        let mut rng = rand::thread_rng();
+        ConvPoly {
+            coeffs: (0..len).map(|_| rng.gen_range(-bound..=bound)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_backends_match_schoolbook() {
+        let mut rng = rand::thread_rng();
+
+        // Sizes around every threshold, with operands of different lengths
+        for n in [
+            1, 2, 3, 4, 5, 7, 8, 31, 63, 64, 65, 101, 255, 256, 257, 509, 661, 821,
+        ] {
+            for _ in 0..3 {
+                let a = random_poly(rng.gen_range(1..=n), 2048);
+                let b = random_poly(rng.gen_range(1..=n), 2048);
+                let expected = a.mul_with(&b, n, MulBackend::Schoolbook);
+                for backend in BACKENDS {
+                    assert_eq!(
+                        expected,
+                        a.mul_with(&b, n, backend),
+                        "{:?} differs from schoolbook at N = {}",
+                        backend,
+                        n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_backends_match_on_unreduced_input() {
+        // Operands longer than N wrap around, and products that overflow an i32 wrap identically
+        for n in [17, 300] {
+            let a = random_poly(2 * n + 3, i32::MAX);
+            let b = random_poly(n + 5, i32::MAX);
+            let expected = a.mul_with(&b, n, MulBackend::Schoolbook);
+            for backend in BACKENDS {
+                assert_eq!(expected, a.mul_with(&b, n, backend), "{:?}", backend);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ternary_times_dense() {
+        // The shape of the products in the scheme: a sparse ternary polynomial times a dense one modulo q
+        let ring = PolyRing::new(821, 4096).unwrap();
+        let r = ring.ternary(255, 255).unwrap();
+        let h = ring.element(&random_poly(821, 4096));
+        let expected = r.try_mul_with(&h, MulBackend::Schoolbook).unwrap();
+        for backend in BACKENDS {
+            assert_eq!(
+                expected,
+                r.try_mul_with(&h, backend).unwrap(),
+                "{:?}",
+                backend
+            );
+        }
+    }
+
+    #[test]
+    fn test_ntt_large_non_power_of_two() {
+        // The zero-padded transform of a 2N - 1 coefficient product is folded back onto x^N - 1
+        let n = 2503;
+        let a = random_poly(n, i32::MAX);
+        let b = random_poly(n, i32::MAX);
+        assert_eq!(
+            a.mul_with(&b, n, MulBackend::Ntt),
+            a.mul_with(&b, n, MulBackend::ToomCook4)
+        );
+    }
+
+    #[test]
+    fn test_zero_and_constants() {
+        let a = random_poly(40, 100);
+        for backend in BACKENDS {
+            assert_eq!(
+                a.mul_with(&ConvPoly::constant(0), 40, backend),
+                ConvPoly::constant(0)
+            );
+            assert_eq!(a.mul_with(&ConvPoly::constant(1), 40, backend), a.trim());
+        }
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod ntru_ciphertext_tests {
+    use ntru_rs::{ntru_util::max_message_len, NtruCiphertext, NtruError, NtruKeyPair, NtruParams};
+    use rand::Rng;
+
+    #[test]
+    fn test_multi_block_encrypt_decrypt() {
+        let params = NtruParams::default();
+        let keypair = NtruKeyPair::new(&params).unwrap();
+
+        // Messages around and well beyond the capacity of a single block
+        let capacity = max_message_len(&params) - 4;
+        for len in [0, 1, capacity, capacity + 1, 3 * capacity, 1000] {
+            let msg: Vec<u8> = (0..len).map(|_| rand::thread_rng().gen()).collect();
+            let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+            assert_eq!(enc_msg.block_count(), len.div_ceil(capacity).max(1));
+            assert_eq!(enc_msg.total_len(), len);
+            assert_eq!(msg, keypair.private.decrypt_to_bytes(&enc_msg).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_fresh_randomness_per_block() {
+        let params = NtruParams::default();
+        let keypair = NtruKeyPair::new(&params).unwrap();
+
+        // Blocks with identical contents (apart from their index) shouldn't share a perturbation
+        let msg = vec![0; 3 * (max_message_len(&params) - 4)];
+        let enc_msg = keypair.public.encrypt_bytes(msg).unwrap();
+        let blocks = enc_msg.blocks();
+        assert_ne!(blocks[1], blocks[2]);
+    }
+
+    #[test]
+    fn test_reordered_and_truncated_blocks() {
+        let params = NtruParams::default();
+        let keypair = NtruKeyPair::new(&params).unwrap();
+        let msg = vec![b'a'; 500];
+        let enc_msg = keypair.public.encrypt_bytes(msg).unwrap();
+        let blocks = enc_msg.blocks().to_vec();
+        assert!(blocks.len() >= 3);
+
+        // Swapping two blocks
+        let mut swapped = blocks.clone();
+        swapped.swap(0, 1);
+        let reordered = NtruCiphertext::new(swapped, enc_msg.total_len());
+        assert!(matches!(
+            keypair.private.decrypt_to_bytes(&reordered),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+
+        // Dropping the last block, whether or not the recorded length is adjusted to match
+        let capacity = max_message_len(&params) - 4;
+        let dropped = blocks[..blocks.len() - 1].to_vec();
+        for total_len in [enc_msg.total_len(), dropped.len() * capacity] {
+            let truncated = NtruCiphertext::new(dropped.clone(), total_len);
+            assert!(matches!(
+                keypair.private.decrypt_to_bytes(&truncated),
+                Err(NtruError::InvalidEncoding(_))
+            ));
+        }
+
+        // Stripping every block, even with the recorded length adjusted to match
+        let stripped = NtruCiphertext::new(Vec::new(), 0);
+        assert!(matches!(
+            keypair.private.decrypt_to_bytes(&stripped),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+
+        // Changing the recorded length
+        let wrong_len = NtruCiphertext::new(blocks, enc_msg.total_len() - 1);
+        assert!(matches!(
+            keypair.private.decrypt_to_bytes(&wrong_len),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_bytecode() {
+        let params = NtruParams::new(251, 3, 127, 6);
+        let keypair = NtruKeyPair::new(&params).unwrap();
+        let msg = vec![0xff; 100];
+        let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+
+        let buf = enc_msg.to_be_bytes();
+        let decoded = NtruCiphertext::from_be_bytes(&buf).unwrap();
+        assert_eq!(enc_msg, decoded);
+        assert_eq!(msg, keypair.private.decrypt_to_bytes(&decoded).unwrap());
+
+        // Truncated buffers, trailing data and a wrong block count are rejected
+        assert!(NtruCiphertext::from_be_bytes(&buf[..buf.len() - 1]).is_err());
+        assert!(NtruCiphertext::from_be_bytes(&buf[..6]).is_err());
+        assert!(NtruCiphertext::from_be_bytes(&[buf.as_slice(), &[0]].concat()).is_err());
+        let mut wrong_count = buf.clone();
+        wrong_count[3] += 1;
+        assert!(NtruCiphertext::from_be_bytes(&wrong_count).is_err());
+
+        // A header recording no blocks and an empty message
+        assert_eq!(
+            NtruCiphertext::from_be_bytes(&[0; 8]),
+            Err(NtruError::InvalidEncoding("ciphertext contains no blocks"))
+        );
+    }
+}
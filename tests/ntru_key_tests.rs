@@ -1,51 +1,66 @@
 #[cfg(test)]
 mod ntru_key_tests {
-    use ntru_rs::{convolution_polynomial::ternary_polynomial, ntru_key::NtruKeyPair, ConvPoly};
-    use rand::Rng; 
+    use ntru_rs::{
+        convolution_polynomial::ternary_polynomial,
+        ntru_key::{NtruKeyPair, NtruPublicKey},
+        ntru_util::max_message_len,
+        params, ConvPoly, NtruCiphertext, NtruError, NtruParams, PolyModQ, ProductFormWeights,
+    };
+    use rand::Rng;
 
     #[test]
     fn test_bytecode() {
         let n = rand::thread_rng().gen_range(1..=15);
         let num_ones = rand::thread_rng().gen_range(0..=n);
         let num_neg_ones = rand::thread_rng().gen_range(0..=(n - num_ones));
-        let poly = ternary_polynomial(n, num_ones, num_neg_ones);
+        let poly = ternary_polynomial(n, num_ones, num_neg_ones).unwrap();
 
         let enc_poly = poly.to_be_bytes();
-        assert!(ConvPoly::from_be_bytes(&enc_poly) == poly, "Failed encoding polynomial")
+        assert!(
+            ConvPoly::from_be_bytes(&enc_poly).unwrap() == poly,
+            "Failed encoding polynomial"
+        )
+    }
+
+    #[test]
+    fn test_bytecode_invalid_length() {
+        // Every coefficient is encoded as 4 bytes
+        assert!(matches!(
+            ConvPoly::from_be_bytes(&[0, 0, 1]),
+            Err(NtruError::InvalidEncoding(_))
+        ));
     }
 
     #[test]
     fn test_ntru_encrypt_decrypt() {
         // "Hello World" message
-        let keypair = NtruKeyPair::new();
+        let keypair = NtruKeyPair::new(&NtruParams::default()).unwrap();
         let msg = "Hello World".as_bytes().to_vec();
         println!("Message: {:?}", msg);
-        let enc_msg = keypair.public.encrypt_bytes(msg.clone());
-        let dec_msg = keypair.private.decrypt_to_bytes(enc_msg);
+        let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        let dec_msg = keypair.private.decrypt_to_bytes(&enc_msg).unwrap();
         println!("Decrypted message: {:?}", dec_msg);
         assert_eq!(msg, dec_msg, "Hello World failed");
 
         // Test empty message
-        let keypair = NtruKeyPair::new();
+        let keypair = NtruKeyPair::new(&NtruParams::default()).unwrap();
         let msg = vec![];
-        let enc_msg = keypair.public.encrypt_bytes(msg.clone());
-        let dec_msg = keypair.private.decrypt_to_bytes(enc_msg);
+        let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        let dec_msg = keypair.private.decrypt_to_bytes(&enc_msg).unwrap();
         assert_eq!(msg, dec_msg, "Empty message failed");
 
         // Test to bytes and out of bytes encrypt
-        let keypair = NtruKeyPair::new();
+        let keypair = NtruKeyPair::new(&NtruParams::default()).unwrap();
         let msg = "helloworld".as_bytes().to_vec();
         println!("message as string: {}", String::from_utf8_lossy(&msg));
         println!("Message 3: {:?}", msg);
-        let enc_msg = keypair.public.encrypt_bytes(msg.clone());
+        let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
         let enc_msg_bytes = enc_msg.to_be_bytes();
-        let enc_msg_debyted = ConvPoly::from_be_bytes(&enc_msg_bytes);
-        let dec_msg = keypair.private.decrypt_to_bytes(enc_msg_debyted);
+        let enc_msg_debyted = NtruCiphertext::from_be_bytes(&enc_msg_bytes).unwrap();
+        let dec_msg = keypair.private.decrypt_to_bytes(&enc_msg_debyted).unwrap();
         println!("dec_msg as string: {}", String::from_utf8_lossy(&dec_msg));
         assert_eq!(msg, dec_msg, "debyting message failed");
 
-
-
         // // Test random messages with new key pairs
         // let num_tests = 100;
         // let mut rng = rand::thread_rng();
@@ -53,10 +68,158 @@ mod ntru_key_tests {
         // for _ in 0..num_tests {
         //     let msg_len = rng.gen_range(0..100);
         //     let msg: Vec<u8> = (0..=msg_len).map(|_| rng.gen_range(1..=127)).collect();
-        //     let keypair = NtruKeyPair::new();
-        //     let enc_msg = keypair.public.encrypt_bytes(msg.clone());
-        //     let dec_msg = keypair.private.decrypt_to_bytes(enc_msg);
+        //     let keypair = NtruKeyPair::new(&NtruParams::default()).unwrap();
+        //     let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        //     let dec_msg = keypair.private.decrypt_to_bytes(&enc_msg).unwrap();
         //     assert_eq!(msg, dec_msg, "Random message failed");
         // }
     }
+
+    #[test]
+    fn test_encrypt_decrypt_binary() {
+        let keypair = NtruKeyPair::new(&NtruParams::default()).unwrap();
+        let msg = vec![0x00, 0xff, 0xf2, 0x00, 0x7f, 0x80, 0x00, 0x00];
+        let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        let dec_msg = keypair.private.decrypt_to_bytes(&enc_msg).unwrap();
+        assert_eq!(msg, dec_msg, "Binary message failed");
+    }
+
+    #[test]
+    fn test_multiple_parameter_sets() {
+        // Key pairs over two different parameter sets should coexist within one process
+        let small_params = NtruParams::new(251, 3, 127, 6);
+        let small_keypair = NtruKeyPair::new(&small_params).unwrap();
+        let default_keypair = NtruKeyPair::new(&NtruParams::default()).unwrap();
+        assert_eq!(small_keypair.public.params(), &small_params);
+        assert_eq!(default_keypair.private.params(), &NtruParams::default());
+
+        let msg = "Hello World".as_bytes().to_vec();
+        let enc_small = small_keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        let enc_default = default_keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        assert_eq!(
+            msg,
+            small_keypair.private.decrypt_to_bytes(&enc_small).unwrap()
+        );
+        assert_eq!(
+            msg,
+            default_keypair
+                .private
+                .decrypt_to_bytes(&enc_default)
+                .unwrap()
+        );
+
+        // A public key deserialized with its parameter set should still encrypt for its private key
+        let public =
+            NtruPublicKey::from_be_bytes(&small_keypair.public.to_be_bytes(), &small_params)
+                .unwrap();
+        let enc_msg = public.encrypt_bytes(msg.clone()).unwrap();
+        assert_eq!(
+            msg,
+            small_keypair.private.decrypt_to_bytes(&enc_msg).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_product_form_keys() {
+        for (params, weights) in [
+            (NtruParams::default(), ProductFormWeights::new(2, 2, 2)),
+            (params::EES401EP1, ProductFormWeights::new(8, 8, 6)),
+        ] {
+            let keypair = NtruKeyPair::new_product_form(&params, &weights).unwrap();
+            assert!(keypair.private.is_product_form());
+            assert!(!NtruKeyPair::new(&params).unwrap().private.is_product_form());
+
+            let msg = "Hello from a product-form key!".as_bytes().to_vec();
+            let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+            assert_eq!(msg, keypair.private.decrypt_to_bytes(&enc_msg).unwrap());
+
+            // Polynomial messages decrypt to all N center-lifted coefficients just like they do with a dense key
+            let poly = ternary_polynomial(params.n, 60, 0).unwrap();
+            let enc_poly = keypair.public.encrypt_poly(poly.clone()).unwrap();
+            let dec_poly = keypair.private.decrypt_to_poly(enc_poly).unwrap();
+            assert_eq!(params.n, dec_poly.coeffs.len());
+            assert_eq!(poly, dec_poly.trim());
+        }
+
+        // Factors that don't fit in the ring are rejected
+        assert!(matches!(
+            NtruKeyPair::new_product_form(
+                &NtruParams::new(11, 3, 127, 2),
+                &ProductFormWeights::new(6, 1, 1)
+            ),
+            Err(NtruError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let params = NtruParams::new(251, 3, 127, 6);
+        let keypair = NtruKeyPair::new(&params).unwrap();
+
+        // A message can be split into at most 65535 blocks, each holding a 4-byte block header
+        let max = (max_message_len(&params) - 4) * u16::MAX as usize;
+        let msg = vec![b'a'; max + 1];
+        assert_eq!(
+            keypair.public.encrypt_bytes(msg.clone()),
+            Err(NtruError::MessageTooLong {
+                len: msg.len(),
+                max
+            })
+        );
+
+        // Bytes that aren't a whole number of coefficients, that don't have N coefficients, or whose coefficients
+        // aren't reduced modulo q aren't a key
+        let key_bytes = keypair.public.to_be_bytes();
+        assert_eq!(key_bytes.len(), 2 * params.n);
+        assert!(NtruPublicKey::from_be_bytes(&key_bytes[1..], &params).is_err());
+        assert!(NtruPublicKey::from_be_bytes(
+            &[key_bytes.clone(), key_bytes.clone()].concat(),
+            &params
+        )
+        .is_err());
+        let mut unreduced = key_bytes;
+        unreduced[..2].copy_from_slice(&(params.q as u16).to_be_bytes());
+        assert!(matches!(
+            NtruPublicKey::from_be_bytes(&unreduced, &params),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+
+        // A ciphertext with more than N coefficients doesn't belong to the parameter set
+        let oversized = NtruCiphertext::new(vec![PolyModQ::new(vec![1; params.n + 1])], 0);
+        assert!(matches!(
+            keypair.private.decrypt_to_bytes(&oversized),
+            Err(NtruError::ParameterMismatch(_))
+        ));
+
+        // Key generation rejects parameters whose weights don't fit in the ring, or whose moduli are too large for
+        // keys to be stored compactly
+        assert!(matches!(
+            NtruKeyPair::new(&NtruParams::new(11, 3, 127, 6)),
+            Err(NtruError::InvalidParameters(_))
+        ));
+
+        // With 2d + 1 = N, f(x) ≡ 1 + x + ... + x^(N-1) (mod 2) is never invertible, so no key exists for an even q
+        assert!(matches!(
+            NtruKeyPair::new(&NtruParams::new(3, 3, 32, 1)),
+            Err(NtruError::InvalidParameters(_))
+        ));
+        assert!(NtruKeyPair::new(&NtruParams::new(3, 3, 31, 1)).is_ok());
+        for params in [
+            NtruParams::new(11, 3, 65537, 2),
+            NtruParams::new(11, 3, 70001, 1),
+            NtruParams::new(11, 256, 65536, 2),
+        ] {
+            assert!(matches!(
+                NtruKeyPair::new(&params),
+                Err(NtruError::InvalidParameters(_))
+            ));
+        }
+
+        // Moduli at the limit still work
+        let params = NtruParams::new(61, 255, 65536, 2);
+        let keypair = NtruKeyPair::new(&params).unwrap();
+        let msg = vec![1, 2, 3];
+        let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+        assert_eq!(msg, keypair.private.decrypt_to_bytes(&enc_msg).unwrap());
+    }
 }
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod ntru_util_tests {
-    use ntru_rs::ntru_util::{deserialize, serialize};
+    use ntru_rs::ntru_util::{deserialize, max_message_len, serialize};
+    use ntru_rs::{ConvPoly, NtruError, NtruParams};
 
     #[test]
     fn test_serialize() {
@@ -8,11 +9,19 @@ mod ntru_util_tests {
         let msg_test_bytes = msg_test.as_bytes().to_vec();
         println!(
             "coeffs for test bytes: {:?}",
-            serialize(msg_test_bytes.clone()).coeffs
+            serialize(msg_test_bytes.clone(), &NtruParams::default())
+                .unwrap()
+                .coeffs
         );
         assert_eq!(
-            serialize(msg_test_bytes).coeffs,
-            vec![1, 0, -1, 1, -1, 1, 0, -1, 0, -1, 1, 1, 0, 0, 0, 1, 1, 0, 0, 0, 1, 1, 0, 1, 0]
+            serialize(msg_test_bytes, &NtruParams::default())
+                .unwrap()
+                .coeffs,
+            // The length 5 as a u16, followed by "hello", with every 3 bits mapped to 2 trits
+            vec![
+                0, 0, 0, 0, 0, 0, 0, 0, 0, -1, 1, -1, 1, -1, 0, 0, 1, 0, 0, 1, 0, -1, -1, 0, -1, 0,
+                0, 1, 1, -1, 1, 1, 1, 0, 1, 0, -1, 0
+            ]
         );
     }
 
@@ -21,12 +30,93 @@ mod ntru_util_tests {
         let msg = "hello guys this is alex";
         let ser_msg = {
             let msg_bytes = String::from(msg).as_bytes().to_vec();
-            serialize(msg_bytes)
+            serialize(msg_bytes, &NtruParams::default()).unwrap()
         };
         println!("Coeffs: {:?}", ser_msg.coeffs);
-        let deser = deserialize(ser_msg);
+        let deser = deserialize(ser_msg, &NtruParams::default()).unwrap();
         println!("deser: {}", String::from_utf8_lossy(&deser));
         assert_eq!(msg.as_bytes().to_vec(), deser);
         println!("characters in message: {}", msg.len());
     }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let params = NtruParams::default();
+
+        // Every byte value survives, including NUL and bytes above 241
+        let msg: Vec<u8> = (0..=255)
+            .cycle()
+            .skip(140)
+            .take(max_message_len(&params))
+            .collect();
+        let ser_msg = serialize(msg.clone(), &params).unwrap();
+        assert!(ser_msg.coeffs.len() <= params.n);
+        assert_eq!(msg, deserialize(ser_msg, &params).unwrap());
+
+        // Leading and trailing zero bytes are kept by the length prefix
+        for msg in [
+            vec![],
+            vec![0],
+            vec![0x80, 0, 0],
+            vec![0, 0, 0xff, 0, 0],
+            vec![0xff; 4],
+        ] {
+            let ser_msg = serialize(msg.clone(), &params).unwrap();
+            assert_eq!(msg, deserialize(ser_msg.trim(), &params).unwrap());
+        }
+
+        // Coefficients may be given in [0, p), as decryption returns them
+        let msg = vec![0xde, 0xad, 0xbe, 0xef];
+        let ser_msg = serialize(msg.clone(), &params).unwrap();
+        assert_eq!(
+            msg,
+            deserialize(ser_msg.modulo(params.p).unwrap(), &params).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_serialization() {
+        let params = NtruParams::default();
+
+        // The trit pair (-1, -1) doesn't represent 3 bits
+        let ser_msg = ConvPoly {
+            coeffs: vec![-1, -1],
+        };
+        assert!(matches!(
+            deserialize(ser_msg, &params),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+
+        // Coefficients outside of {-1, 0, 1} aren't ternary
+        let ser_msg = ConvPoly {
+            coeffs: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2],
+        };
+        let params_p7 = NtruParams::new(params.n, 7, params.q, params.d);
+        assert!(matches!(
+            deserialize(ser_msg, &params_p7),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+
+        // A length prefix longer than fits within the polynomial
+        let ser_msg = ConvPoly {
+            coeffs: vec![1, 1, 1, 1, 1, 1],
+        };
+        assert!(matches!(
+            deserialize(ser_msg, &params),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+
+        let msg = vec![0; max_message_len(&params) + 1];
+        assert!(matches!(
+            serialize(msg, &params),
+            Err(NtruError::MessageTooLong { .. })
+        ));
+
+        // p = 2 can't hold ternary coefficients
+        let params_p2 = NtruParams::new(params.n, 2, params.q, params.d);
+        assert!(matches!(
+            serialize(vec![1], &params_p2),
+            Err(NtruError::InvalidParameters(_))
+        ));
+    }
 }
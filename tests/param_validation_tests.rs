@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod param_validation_tests {
+    use ntru_rs::param_validation::{validate, CheckStatus, ParamCheck};
+    use ntru_rs::{NtruParams, ParamSet};
+
+    #[test]
+    fn test_default_params_are_correct() {
+        // (6 * 21 + 1) * 3 = 381 < 383, so decryption never fails
+        let report = NtruParams::default().validate();
+        println!("{}", report);
+        assert!(report.is_valid(), "Default parameters should be valid");
+        assert!(report.is_correct(), "Default parameters should be correct");
+        assert_eq!(report.checks.len(), 5);
+    }
+
+    #[test]
+    fn test_failing_checks() {
+        // p and q share a factor
+        let report = validate(&NtruParams::new(661, 3, 384, 21));
+        assert_eq!(
+            report.status(ParamCheck::CoprimeModuli),
+            Some(CheckStatus::Fail)
+        );
+        assert!(!report.is_valid());
+
+        // Non-positive moduli
+        let report = validate(&NtruParams::new(661, 0, 383, 21));
+        assert_eq!(
+            report.status(ParamCheck::CoprimeModuli),
+            Some(CheckStatus::Fail)
+        );
+
+        // N is composite
+        let report = validate(&NtruParams::new(660, 3, 383, 21));
+        assert_eq!(report.status(ParamCheck::PrimeN), Some(CheckStatus::Fail));
+        assert!(!report.is_valid());
+
+        // f(x) doesn't fit in N coefficients
+        let report = validate(&NtruParams::new(11, 3, 383, 6));
+        assert_eq!(
+            report.status(ParamCheck::WeightFitsRing),
+            Some(CheckStatus::Fail)
+        );
+        assert_eq!(
+            report.status(ParamCheck::DecryptionBound),
+            Some(CheckStatus::Pass)
+        );
+    }
+
+    #[test]
+    fn test_ring_splitting() {
+        // Modulo 3, ord_661(3) = 22, so (x^661 - 1)/(x - 1) splits into 30 factors of degree 22
+        let report = NtruParams::default().validate();
+        assert_eq!(
+            report.status(ParamCheck::RingSplitting),
+            Some(CheckStatus::Warning)
+        );
+        let result = &report.checks[4];
+        assert_eq!(
+            result.detail,
+            "modulo 3, 30 factors of degree 22; modulo 383, 4 factors of degree 165"
+        );
+        assert!(report.is_valid() && report.is_correct());
+
+        // 2 and 3 both have order N - 1 modulo 509, so (x^509 - 1)/(x - 1) is irreducible
+        let report = ParamSet::NtruHps2048509.params().validate();
+        assert_eq!(
+            report.status(ParamCheck::RingSplitting),
+            Some(CheckStatus::Pass)
+        );
+        assert_eq!(
+            report.checks[4].detail,
+            "modulo 2, 1 factor of degree 508; modulo 3, 1 factor of degree 508"
+        );
+
+        // 3 divides N, so x^N - 1 has repeated factors
+        let report = validate(&NtruParams::new(9, 3, 32, 1));
+        assert_eq!(
+            report.status(ParamCheck::RingSplitting),
+            Some(CheckStatus::Warning)
+        );
+        assert_eq!(
+            report.checks[4].detail,
+            "modulo 2, 1 factor of degree 2 and 1 factor of degree 6; \
+             modulo 3, no factors besides x - 1 (every factor repeated 9 times)"
+        );
+
+        let report = validate(&NtruParams::new(0, 3, 32, 0));
+        assert_eq!(
+            report.status(ParamCheck::RingSplitting),
+            Some(CheckStatus::Fail)
+        );
+    }
+
+    #[test]
+    fn test_probabilistic_decryption_bound() {
+        // (6 * 22 + 1) * 3 = 399 >= 383
+        let report = validate(&NtruParams::new(661, 3, 383, 22));
+        assert_eq!(
+            report.status(ParamCheck::DecryptionBound),
+            Some(CheckStatus::Probabilistic)
+        );
+        assert!(report.is_valid(), "Probabilistic checks are not failures");
+        assert!(!report.is_correct());
+
+        // Most of the standardized sets rely on decryption succeeding with high probability
+        for set in ParamSet::ALL {
+            let report = set.params().validate();
+            assert!(report.is_valid(), "{} should be valid", set.name());
+        }
+        let report = ParamSet::Ees401Ep1.params().validate();
+        assert_eq!(
+            report.status(ParamCheck::DecryptionBound),
+            Some(CheckStatus::Probabilistic)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod params_tests {
+    use ntru_rs::{params::DEFAULT_PARAMS, NtruKeyPair, NtruParams, ParamSet};
+
+    #[test]
+    fn test_default_params() {
+        assert_eq!(NtruParams::default(), NtruParams::new(661, 3, 383, 21));
+        assert_eq!(NtruParams::default(), DEFAULT_PARAMS);
+    }
+
+    #[test]
+    fn test_preset_lookup() {
+        for set in ParamSet::ALL {
+            assert_eq!(
+                ParamSet::from_name(set.name()),
+                Some(set),
+                "Lookup by name failed"
+            );
+            assert_eq!(
+                ParamSet::from_id(set.id()),
+                Some(set),
+                "Lookup by ID failed"
+            );
+        }
+
+        // Names are matched regardless of case
+        assert_eq!(ParamSet::from_name("EES401EP1"), Some(ParamSet::Ees401Ep1));
+        assert_eq!(
+            ParamSet::from_name("NtruHps4096821"),
+            Some(ParamSet::NtruHps4096821)
+        );
+
+        // Unknown presets
+        assert_eq!(ParamSet::from_name("ees1087ep2"), None);
+        assert_eq!(ParamSet::from_id(0), None);
+        assert_eq!(ParamSet::from_id(9), None);
+
+        let params: NtruParams = ParamSet::NtruHps2048509.into();
+        assert_eq!(params, NtruParams::new(509, 3, 2048, 127));
+    }
+
+    #[test]
+    fn test_preset_encrypt_decrypt() {
+        let msg = "Hello World".as_bytes().to_vec();
+
+        for set in ParamSet::ALL {
+            let keypair = NtruKeyPair::new(&set.params()).unwrap();
+            let enc_msg = keypair.public.encrypt_bytes(msg.clone()).unwrap();
+            let dec_msg = keypair.private.decrypt_to_bytes(&enc_msg).unwrap();
+            assert_eq!(msg, dec_msg, "Encryption with {} failed", set.name());
+        }
+    }
+}
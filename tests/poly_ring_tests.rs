@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod poly_ring_tests {
+    use ntru_rs::{ConvPoly, MulBackend, NtruError, PolyRing, ReductionPoly};
+    use rand::Rng;
+
+    #[test]
+    fn test_new_ring() {
+        let ring = PolyRing::new(7, 11).unwrap();
+        assert_eq!((ring.n(), ring.modulus()), (7, 11));
+        assert_eq!(ring.to_string(), "(Z/11Z)[x]/(x^7 - 1)");
+
+        assert!(PolyRing::new(0, 11).is_err());
+        assert_eq!(PolyRing::new(7, 0), Err(NtruError::InvalidModulus(0)));
+    }
+
+    #[test]
+    fn test_element_is_reduced() {
+        let ring = PolyRing::new(5, 7).unwrap();
+
+        // Coefficients are reduced modulo 7 and padded to 5 entries
+        let a = ring.element(&ConvPoly {
+            coeffs: vec![-1, 8, 14],
+        });
+        assert_eq!(a.coeffs(), &[6, 1, 0, 0, 0]);
+
+        // x^5 = 1, so x^5 + 2x^6 wraps around to 1 + 2x
+        let b = ring.element(&ConvPoly {
+            coeffs: vec![0, 0, 0, 0, 0, 1, 2],
+        });
+        assert_eq!(b.coeffs(), &[1, 2, 0, 0, 0]);
+
+        assert!(ring.zero().is_zero());
+        assert_eq!(ring.constant(-2).coeffs(), &[5, 0, 0, 0, 0]);
+        assert_eq!(a.center_lift().coeffs, vec![-1, 1]);
+        assert_eq!(a.to_poly().coeffs, vec![6, 1]);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let ring = PolyRing::new(5, 11).unwrap();
+        let a = ring.element(&ConvPoly {
+            coeffs: vec![1, 2, 3, 4, 5],
+        });
+        let b = ring.element(&ConvPoly {
+            coeffs: vec![10, 9, 0, 0, 1],
+        });
+
+        assert_eq!(a.try_add(&b).unwrap().coeffs(), &[0, 0, 3, 4, 6]);
+        assert_eq!(a.try_sub(&b).unwrap().coeffs(), &[2, 4, 3, 4, 4]);
+        assert_eq!((-&a).coeffs(), &[10, 9, 8, 7, 6]);
+        assert_eq!(a.scale(3).coeffs(), &[3, 6, 9, 1, 4]);
+
+        // The product matches a multiplication over Z followed by a reduction
+        let expected = ConvPoly {
+            coeffs: vec![1, 2, 3, 4, 5],
+        }
+        .mul(
+            &ConvPoly {
+                coeffs: vec![10, 9, 0, 0, 1],
+            },
+            5,
+        );
+        assert_eq!(a.try_mul(&b).unwrap(), ring.element(&expected));
+    }
+
+    #[test]
+    fn test_operators() {
+        let ring = PolyRing::new(7, 383).unwrap();
+        let m = ring.element(&ConvPoly {
+            coeffs: vec![1, -1, 0, 1, 1],
+        });
+        let r = ring.ternary(2, 2).unwrap();
+        let h = ring.element(&ConvPoly {
+            coeffs: vec![17, 250, 3, 99, 0, 301, 42],
+        });
+
+        // Every combination of owned and borrowed operands agrees with the try_* methods
+        let sum = m.try_add(&h).unwrap();
+        assert_eq!(&m + &h, sum);
+        assert_eq!(m.clone() + &h, sum);
+        assert_eq!(&m + h.clone(), sum);
+        assert_eq!(m.clone() + h.clone(), sum);
+        let diff = m.try_sub(&h).unwrap();
+        assert_eq!(&m - &h, diff);
+        assert_eq!(m.clone() - h.clone(), diff);
+        let product = r.try_mul(&h).unwrap();
+        assert_eq!(&r * &h, product);
+        assert_eq!(r.clone() * h.clone(), product);
+        assert_eq!(-(-m.clone()), m);
+
+        // Compound assignment
+        let mut acc = m.clone();
+        acc += &h;
+        acc -= h.clone();
+        assert_eq!(acc, m);
+        acc *= &r;
+        assert_eq!(acc, &m * &r);
+        acc *= 3;
+        assert_eq!(acc, 3 * (&m * &r));
+
+        // The textbook encryption formula e(x) = m(x) + p*r(x)*h(x)
+        let e = &m + 3 * &r * &h;
+        let expected = m.try_add(&r.scale(3).try_mul(&h).unwrap()).unwrap();
+        assert_eq!(e, expected);
+        assert_eq!(&r * 3, 3 * &r);
+    }
+
+    #[test]
+    #[should_panic(expected = "elements of different rings can't be combined")]
+    fn test_operator_ring_mismatch() {
+        let ring_q = PolyRing::new(7, 383).unwrap();
+        let ring_p = PolyRing::new(7, 3).unwrap();
+        let _ = ring_q.constant(1) * ring_p.constant(1);
+    }
+
+    #[test]
+    fn test_large_modulus_mul() {
+        // Products of coefficients this large overflow an i32
+        let ring = PolyRing::new(3, 2_147_483_647).unwrap();
+        let a = ring.constant(2_147_483_646);
+        assert_eq!(a.try_mul(&a).unwrap(), ring.constant(1));
+    }
+
+    #[test]
+    fn test_inverse() {
+        for modulus in [3, 2048, 243, 15] {
+            let ring = PolyRing::new(11, modulus).unwrap();
+            // f(x) in T(4, 3) is invertible with high probability; retry until one is found
+            let (f, f_inv) = loop {
+                let f = ring.ternary(4, 3).unwrap();
+                if let Ok(f_inv) = f.inverse() {
+                    break (f, f_inv);
+                }
+            };
+            assert_eq!(f.try_mul(&f_inv).unwrap(), ring.constant(1));
+        }
+
+        let ring = PolyRing::new(5, 7).unwrap();
+        assert_eq!(ring.zero().inverse(), Err(NtruError::NotInvertible));
+    }
+
+    /// The reduction polynomials exercised below: x^7 - 1, x^7 + 1, Φ_7, x^6 + x^2 + 1 (which is divisible by x^2 + x
+    /// + 1 over Z/3Z) and x^6 - x^3 + x (which is divisible by x)
+    fn reductions() -> Vec<ReductionPoly> {
+        vec![
+            ReductionPoly::Cyclic(7),
+            ReductionPoly::Negacyclic(7),
+            ReductionPoly::Cyclotomic(7),
+            ReductionPoly::Monic(vec![1, 0, 1, 0, 0, 0, 1].into()),
+            ReductionPoly::Monic(vec![0, 1, 0, -1, 0, 0, 1].into()),
+        ]
+    }
+
+    #[test]
+    fn test_reduction_polys() {
+        let negacyclic = PolyRing::with_reduction(ReductionPoly::Negacyclic(5), 7).unwrap();
+        assert_eq!(negacyclic.to_string(), "(Z/7Z)[x]/(x^5 + 1)");
+        // x^5 = -1, so x^5 + 2x^6 wraps around to -1 - 2x
+        let a = negacyclic.element(&ConvPoly {
+            coeffs: vec![0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 3],
+        });
+        assert_eq!(a.coeffs(), &[2, 5, 0, 0, 0]);
+
+        let cyclotomic = PolyRing::with_reduction(ReductionPoly::Cyclotomic(5), 7).unwrap();
+        assert_eq!(cyclotomic.to_string(), "(Z/7Z)[x]/(Φ_5)");
+        assert_eq!(cyclotomic.n(), 4);
+        // x^4 = -(x^3 + x^2 + x + 1) and x^5 = 1
+        let a = cyclotomic.element(&ConvPoly {
+            coeffs: vec![0, 0, 0, 0, 1, 2],
+        });
+        assert_eq!(a.coeffs(), &[1, 6, 6, 6]);
+
+        let monic = ReductionPoly::Monic(vec![-1, -1, 0, 1].into());
+        let ring = PolyRing::with_reduction(monic, 7).unwrap();
+        assert_eq!(ring.to_string(), "(Z/7Z)[x]/(x^3 - x - 1)");
+        // x^3 = x + 1 and x^4 = x^2 + x
+        let a = ring.element(&ConvPoly {
+            coeffs: vec![0, 0, 0, 1, 1],
+        });
+        assert_eq!(a.coeffs(), &[1, 2, 1]);
+
+        for reduction in [
+            ReductionPoly::Negacyclic(0),
+            ReductionPoly::Cyclotomic(1),
+            ReductionPoly::Monic(vec![1].into()),
+            ReductionPoly::Monic(vec![1, 2].into()),
+        ] {
+            assert!(matches!(
+                PolyRing::with_reduction(reduction, 7),
+                Err(NtruError::InvalidParameters(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_reduction_poly_mul() {
+        let mut rng = rand::thread_rng();
+        for reduction in reductions() {
+            let ring = PolyRing::with_reduction(reduction, 2048).unwrap();
+            let n = ring.n();
+            let random = || ConvPoly {
+                coeffs: (0..n)
+                    .map(|_| rand::thread_rng().gen_range(-5000..5000))
+                    .collect(),
+            };
+            let (a, b) = (random(), random());
+
+            // The product over Z (which no reduction modulo x^2n - 1 can affect) reduced into the ring
+            let expected = ring.element(&a.mul(&b, 2 * n));
+            let (a, b) = (ring.element(&a), ring.element(&b));
+            for backend in [
+                MulBackend::Schoolbook,
+                MulBackend::Karatsuba,
+                MulBackend::Ntt,
+            ] {
+                assert_eq!(a.try_mul_with(&b, backend).unwrap(), expected, "{}", ring);
+            }
+
+            // Center-lifting keeps the element's residue
+            let c = ring.element(&ConvPoly {
+                coeffs: (0..n).map(|_| rng.gen_range(0..2048)).collect(),
+            });
+            assert_eq!(ring.element(&c.center_lift()), c);
+        }
+    }
+
+    #[test]
+    fn test_reduction_poly_inverse() {
+        for reduction in reductions() {
+            for modulus in [3, 7, 2048, 15] {
+                let ring = PolyRing::with_reduction(reduction.clone(), modulus).unwrap();
+                let f = ConvPoly {
+                    coeffs: reduction.coeffs(),
+                };
+                let mut num_inverse_found = 0;
+                for _ in 0..50 {
+                    let a = ring.ternary(3, 2).unwrap();
+
+                    // a(x) is invertible exactly when it's coprime to f(x) modulo every prime dividing the modulus
+                    let invertible =
+                        [2, 3, 5, 7]
+                            .into_iter()
+                            .filter(|p| modulus % p == 0)
+                            .all(|p| {
+                                ConvPoly::gcd(&a.to_poly(), &f, p, 2 * ring.n())
+                                    .unwrap()
+                                    .deg()
+                                    == 0
+                            });
+                    assert_eq!(a.is_invertible(), invertible, "{}", ring);
+                    match a.inverse() {
+                        Ok(inverse) => {
+                            assert!(invertible, "{}", ring);
+                            assert_eq!(&a * &inverse, ring.constant(1), "{}", ring);
+                            num_inverse_found += 1;
+                        }
+                        Err(err) => {
+                            assert_eq!(err, NtruError::NotInvertible);
+                            assert!(!invertible, "{}", ring);
+                        }
+                    }
+                }
+                println!(
+                    "Found inverses for {} elements of {}",
+                    num_inverse_found, ring
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ring_mismatch() {
+        let ring_q = PolyRing::new(7, 383).unwrap();
+        let ring_p = PolyRing::new(7, 3).unwrap();
+        let ring_n = PolyRing::new(11, 383).unwrap();
+
+        let a = ring_q.constant(2);
+        for other in [ring_p.constant(2), ring_n.constant(2)] {
+            assert_eq!(a.try_add(&other), Err(NtruError::RingMismatch));
+            assert_eq!(a.try_sub(&other), Err(NtruError::RingMismatch));
+            assert_eq!(a.try_mul(&other), Err(NtruError::RingMismatch));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod ring_factorization_tests {
+    use ntru_rs::ring_factorization::{
+        cyclotomic_cosets, factor_degrees, multiplicative_order, CyclicFactorization,
+    };
+    use ntru_rs::{ConvPoly, NtruError};
+    use rand::Rng;
+
+    #[test]
+    fn test_multiplicative_order() {
+        assert_eq!(multiplicative_order(2, 7), Some(3));
+        assert_eq!(multiplicative_order(3, 661), Some(22));
+        assert_eq!(multiplicative_order(2, 2048 + 1), Some(22));
+        assert_eq!(multiplicative_order(-1, 5), Some(2));
+        assert_eq!(multiplicative_order(5, 1), Some(1));
+        assert_eq!(multiplicative_order(2, 6), None);
+        assert_eq!(multiplicative_order(2, 0), None);
+
+        assert_eq!(
+            cyclotomic_cosets(2, 7),
+            Some(vec![vec![0], vec![1, 2, 4], vec![3, 6, 5]])
+        );
+        assert_eq!(cyclotomic_cosets(3, 6), None);
+    }
+
+    #[test]
+    fn test_factor_degrees() {
+        assert_eq!(factor_degrees(7, 2), Ok(vec![1, 3, 3]));
+        // x^661 - 1 = (x - 1) times 30 factors of degree 22 over F_3
+        let degrees = factor_degrees(661, 3).unwrap();
+        assert_eq!(degrees.len(), 31);
+        assert_eq!(degrees[0], 1);
+        assert!(degrees[1..].iter().all(|&k| k == 22));
+        // x^6 - 1 = (x^2 - 1)^3 = (x - 1)^3 (x + 1)^3 over F_3
+        assert_eq!(factor_degrees(6, 3), Ok(vec![1, 1]));
+
+        assert!(matches!(
+            factor_degrees(7, 4),
+            Err(NtruError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            factor_degrees(0, 2),
+            Err(NtruError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_factorization() {
+        let factorization = CyclicFactorization::new(7, 2).unwrap();
+        assert_eq!(
+            factorization.to_string(),
+            "x^7 - 1 = (x + 1)(x^3 + x + 1)(x^3 + x^2 + 1) over F_2"
+        );
+
+        for p in [2, 3, 5, 7, 383] {
+            for n in 1..=40 {
+                let factorization = CyclicFactorization::new(n, p).unwrap();
+                let factors = factorization.factors();
+                // As many factors as cyclotomic cosets, of the same degrees, means every factor is irreducible
+                assert_eq!(factorization.degrees(), factor_degrees(n, p).unwrap());
+                assert!(factors.iter().all(|f| f.lc() == 1));
+
+                // The factors, each raised to the multiplicity, multiply to x^n - 1
+                let product = factors.iter().fold(ConvPoly::constant(1), |acc, f| {
+                    (0..factorization.multiplicity())
+                        .fold(acc, |acc, _| acc.mul_mod(f, 2 * n + 1, p).unwrap())
+                });
+                let mut expected = vec![0; n + 1];
+                (expected[0], expected[n]) = (p - 1, 1);
+                assert_eq!(product.coeffs, expected, "{}", factorization);
+            }
+        }
+    }
+
+    #[test]
+    fn test_factorization_standard_size() {
+        let factorization = CyclicFactorization::new(661, 3).unwrap();
+        assert_eq!(factorization.degrees(), factor_degrees(661, 3).unwrap());
+    }
+
+    #[test]
+    fn test_inverse_matches_euclidean() {
+        let mut rng = rand::thread_rng();
+        for p in [2, 3, 7] {
+            for n in [5, 6, 7, 9, 11, 21] {
+                let factorization = CyclicFactorization::new(n, p).unwrap();
+                for _ in 0..20 {
+                    let poly = ConvPoly {
+                        coeffs: (0..n).map(|_| rng.gen_range(-1..=1)).collect(),
+                    };
+                    assert_eq!(
+                        factorization.inverse(&poly),
+                        poly.inverse(p, n),
+                        "{} in (Z/{}Z)[x]/(x^{} - 1)",
+                        poly,
+                        p,
+                        n
+                    );
+                }
+            }
+        }
+    }
+}
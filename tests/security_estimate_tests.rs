@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod security_estimate_tests {
+    use ntru_rs::security_estimate::estimate_security;
+    use ntru_rs::{NtruParams, ParamSet};
+
+    #[test]
+    fn test_estimate_default_params() {
+        let params = NtruParams::default();
+        let estimate = estimate_security(&params).unwrap();
+        println!("{}", estimate);
+
+        assert_eq!(estimate.lattice_dimension, 2 * params.n);
+        assert!(estimate.primal.dimension <= estimate.lattice_dimension);
+        assert!(estimate.classical_bits <= estimate.primal.classical_bits);
+        assert!(estimate.classical_bits <= estimate.hybrid.classical_bits);
+        assert!(estimate.quantum_bits <= estimate.primal.classical_bits);
+    }
+
+    #[test]
+    fn test_estimate_presets() {
+        for set in ParamSet::ALL {
+            let estimate = estimate_security(&set.params()).unwrap();
+            println!("{}: {}", set.name(), estimate);
+            assert!(estimate.classical_bits.is_finite() && estimate.classical_bits > 0.0);
+            assert!(estimate.quantum_bits <= estimate.classical_bits);
+        }
+
+        // The largest sets should be harder to break than the smallest
+        let ees401ep1 = estimate_security(&ParamSet::Ees401Ep1.params()).unwrap();
+        let ntruhps4096821 = estimate_security(&ParamSet::NtruHps4096821.params()).unwrap();
+        assert!(ees401ep1.classical_bits < ntruhps4096821.classical_bits);
+    }
+
+    #[test]
+    fn test_security_grows_with_dimension() {
+        let small = estimate_security(&NtruParams::new(401, 3, 2048, 133)).unwrap();
+        let large = estimate_security(&NtruParams::new(743, 3, 2048, 247)).unwrap();
+        assert!(small.primal.block_size < large.primal.block_size);
+        assert!(small.classical_bits < large.classical_bits);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        assert!(estimate_security(&NtruParams::new(0, 3, 2048, 0)).is_err());
+        assert!(estimate_security(&NtruParams::new(401, 3, 1, 133)).is_err());
+
+        // Rings this small are outside of what the BKZ cost model covers
+        assert!(estimate_security(&NtruParams::new(11, 3, 127, 2)).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod sparse_ternary_tests {
+    use ntru_rs::{ConvPoly, NtruError, PolyRing, ReductionPoly, SparseTernaryPoly};
+    use rand::Rng;
+
+    #[test]
+    fn test_random() {
+        let sparse = SparseTernaryPoly::random(101, 34, 33).unwrap();
+        assert_eq!(sparse.n(), 101);
+        assert_eq!((sparse.ones().len(), sparse.neg_ones().len()), (34, 33));
+        assert!(sparse.ones().iter().all(|i| !sparse.neg_ones().contains(i)));
+        assert!(sparse
+            .ones()
+            .iter()
+            .chain(sparse.neg_ones())
+            .all(|&i| i < 101));
+
+        assert!(SparseTernaryPoly::random(0, 0, 0).is_err());
+        assert!(SparseTernaryPoly::random(5, 3, 3).is_err());
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let poly = ConvPoly {
+            coeffs: vec![0, 1, -1, 0, 1],
+        };
+        let sparse = SparseTernaryPoly::from_poly(&poly, 7).unwrap();
+        assert_eq!(sparse.ones(), &[1, 4]);
+        assert_eq!(sparse.neg_ones(), &[2]);
+        assert_eq!(sparse.to_poly(), poly);
+
+        assert!(matches!(
+            SparseTernaryPoly::from_poly(&ConvPoly::constant(2), 7),
+            Err(NtruError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            SparseTernaryPoly::from_poly(&poly, 4),
+            Err(NtruError::ParameterMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_mul_matches_dense() {
+        let mut rng = rand::thread_rng();
+        for (n, q, d) in [
+            (1, 7, 0),
+            (11, 32, 3),
+            (107, 41, 12),
+            (509, 2048, 127),
+            (661, 383, 21),
+        ] {
+            // Φ_(N + 1) has degree N
+            for reduction in [
+                ReductionPoly::Cyclic(n),
+                ReductionPoly::Negacyclic(n),
+                ReductionPoly::Cyclotomic(n + 1),
+            ] {
+                let ring = PolyRing::with_reduction(reduction, q).unwrap();
+                let sparse = SparseTernaryPoly::random(n, d + 1, d).unwrap();
+                let dense = ring.element(&ConvPoly {
+                    coeffs: (0..n).map(|_| rng.gen_range(0..q)).collect(),
+                });
+
+                let expected = ring.element(&sparse.to_poly()) * &dense;
+                assert_eq!(expected, &sparse * &dense, "{}", ring);
+                assert_eq!(expected, &dense * &sparse, "{}", ring);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_ring_mismatch() {
+        let sparse = SparseTernaryPoly::random(7, 2, 2).unwrap();
+        let ring = PolyRing::new(11, 383).unwrap();
+        assert_eq!(
+            sparse.try_mul(&ring.constant(1)),
+            Err(NtruError::RingMismatch)
+        );
+    }
+}